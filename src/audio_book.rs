@@ -1,75 +1,230 @@
 use chrono::NaiveDate;
-use serde::Serialize;
+use serde::{Serialize, Serializer};
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
 use url::Url;
 
+use crate::{format_runtime, Author, CoverImage, Narrator, Prices, ProductKind, Rating, Series};
+
 /// Represents an audio book with relevant metadata and serialization capabilities.
 ///
 /// # Fields
 ///
+/// * `asin` - The Amazon Standard Identification Number, which uniquely identifies the audio book.
+/// * `url` - The canonical product page of the audio book, if known.
+/// * `kind` - Whether the product is an audiobook, a podcast or an Audible Original.
 /// * `title` - The title of the audio book.
-/// * `narrator` - The name of the person who narrates the audio book.
+/// * `subtitle` - The subtitle of the audio book, if any.
+/// * `authors` - The authors credited on the audio book, including translators.
+/// * `narrators` - The people who narrate the audio book, the first one being the primary narrator.
 /// * `language` - The language in which the audio book is narrated. e.g. "Español (Castellano)".
 /// * `release_date` - The release date of the audio book, if available.
-/// * `sample_url` - A URL to a sample of the audio book.
+/// * `series` - The series the audio book belongs to, with its position in each of them.
+/// * `runtime` - The length of the audio book, if available.
+/// * `rating` - The average listener rating and number of ratings, or `None` if not rated yet.
+/// * `prices` - The regular, sale and member prices shown for the audio book.
+/// * `covers` - The cover image at each of the sizes served by Audible, smallest first.
+/// * `sample_url` - A URL to a sample of the audio book, if it has one.
+///
+/// # Identity
+///
+/// Two audio books are equal, and hash the same, when they have the same ASIN, so collections of
+/// audio books can be deduplicated across pages and runs.
 ///
 /// # Serialization
 ///
-/// This struct can be serialized to JSON with camelCase field names.
+/// This struct can be serialized to JSON with camelCase field names. The runtime is serialized
+/// as an object with the number of `seconds` and a human readable `text`, e.g. `"12 h 34 min"`.
 ///
 /// # Examples
 ///
 /// ```
 /// use chrono::NaiveDate;
 /// use url::Url;
-/// use audible_scraper::AudioBook;
+/// use audible_scraper::{AudioBook, Narrator, ProductKind};
 ///
+/// let asin = String::from("B07N1B5Z8H");
 /// let title = String::from("The Rust Programming Language");
-/// let narrator = String::from("Steve Klabnik");
+/// let narrators = vec![
+///     Narrator::new(String::from("Steve Klabnik"), None),
+///     Narrator::new(String::from("Carol Nichols"), None),
+/// ];
 /// let language = String::from("English");
 /// let release_date = Some(NaiveDate::from_ymd(2019, 5, 15));
-/// let sample_url = Url::parse("https://example.com/sample").unwrap();
+/// let sample_url = Url::parse("https://example.com/sample").ok();
 ///
-/// let audio_book = AudioBook::new(title, narrator, language, release_date, sample_url);
+/// let audio_book = AudioBook::new(asin, title, narrators, language, release_date, sample_url);
 ///
+/// assert_eq!(audio_book.asin(), "B07N1B5Z8H");
 /// assert_eq!(audio_book.title(), "The Rust Programming Language");
 /// assert_eq!(audio_book.narrator(), "Steve Klabnik");
+/// assert_eq!(audio_book.narrators().len(), 2);
 /// assert_eq!(audio_book.language(), "English");
 /// assert_eq!(audio_book.release_date(), Some(NaiveDate::from_ymd(2019, 5, 15)));
-/// assert_eq!(audio_book.sample_url().as_str(), "https://example.com/sample");
+/// assert_eq!(audio_book.sample_url().map(Url::as_str), Some("https://example.com/sample"));
+/// assert!(audio_book.authors().is_empty());
+/// assert_eq!(audio_book.runtime(), None);
+/// assert!(audio_book.series().is_empty());
+/// assert_eq!(audio_book.rating(), None);
+/// assert_eq!(audio_book.url(), None);
+/// assert_eq!(audio_book.prices().regular(), None);
+/// assert_eq!(audio_book.kind(), ProductKind::Audiobook);
+/// assert_eq!(audio_book.subtitle(), None);
+/// assert!(audio_book.covers().is_empty());
 /// ```
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct AudioBook {
+    asin: String,
+    url: Option<Url>,
+    kind: ProductKind,
     title: String,
-    narrator: String,
+    subtitle: Option<String>,
+    authors: Vec<Author>,
+    narrators: Vec<Narrator>,
     language: String,
     release_date: Option<NaiveDate>,
-    sample_url: Url,
+    series: Vec<Series>,
+    #[serde(serialize_with = "serialize_runtime")]
+    runtime: Option<Duration>,
+    rating: Option<Rating>,
+    prices: Prices,
+    covers: Vec<CoverImage>,
+    sample_url: Option<Url>,
+}
+
+fn serialize_runtime<S: Serializer>(
+    runtime: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct Runtime {
+        seconds: u64,
+        text: String,
+    }
+
+    runtime
+        .map(|runtime| Runtime {
+            seconds: runtime.as_secs(),
+            text: format_runtime(runtime),
+        })
+        .serialize(serializer)
 }
 
 impl AudioBook {
     pub fn new(
+        asin: String,
         title: String,
-        narrator: String,
+        narrators: Vec<Narrator>,
         language: String,
         release_date: Option<NaiveDate>,
-        sample_url: Url,
+        sample_url: Option<Url>,
     ) -> Self {
         Self {
+            asin,
+            url: None,
+            kind: ProductKind::default(),
             title,
-            narrator,
+            subtitle: None,
+            authors: Vec::new(),
+            narrators,
             language,
             release_date,
+            series: Vec::new(),
+            runtime: None,
+            rating: None,
+            prices: Prices::default(),
+            covers: Vec::new(),
             sample_url,
         }
     }
 
+    /// Sets the canonical product page of the audio book.
+    pub fn with_url(mut self, url: Url) -> Self {
+        self.url = Some(url);
+        self
+    }
+
+    /// Sets the kind of product.
+    pub fn with_kind(mut self, kind: ProductKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Sets the subtitle of the audio book.
+    pub fn with_subtitle(mut self, subtitle: Option<String>) -> Self {
+        self.subtitle = subtitle;
+        self
+    }
+
+    /// Sets the cover images of the audio book.
+    pub fn with_covers(mut self, covers: Vec<CoverImage>) -> Self {
+        self.covers = covers;
+        self
+    }
+
+    /// Sets the authors credited on the audio book.
+    pub fn with_authors(mut self, authors: Vec<Author>) -> Self {
+        self.authors = authors;
+        self
+    }
+
+    /// Sets the series the audio book belongs to.
+    pub fn with_series(mut self, series: Vec<Series>) -> Self {
+        self.series = series;
+        self
+    }
+
+    /// Sets the length of the audio book.
+    pub fn with_runtime(mut self, runtime: Option<Duration>) -> Self {
+        self.runtime = runtime;
+        self
+    }
+
+    /// Sets the listener rating of the audio book.
+    pub fn with_rating(mut self, rating: Option<Rating>) -> Self {
+        self.rating = rating;
+        self
+    }
+
+    /// Sets the prices shown for the audio book.
+    pub fn with_prices(mut self, prices: Prices) -> Self {
+        self.prices = prices;
+        self
+    }
+
+    pub fn asin(&self) -> &str {
+        &self.asin
+    }
+
+    pub fn url(&self) -> Option<&Url> {
+        self.url.as_ref()
+    }
+
+    pub fn kind(&self) -> ProductKind {
+        self.kind
+    }
+
     pub fn title(&self) -> &str {
         &self.title
     }
 
+    pub fn subtitle(&self) -> Option<&str> {
+        self.subtitle.as_deref()
+    }
+
+    pub fn authors(&self) -> &[Author] {
+        &self.authors
+    }
+
+    pub fn narrators(&self) -> &[Narrator] {
+        &self.narrators
+    }
+
+    /// Name of the primary narrator, the first one credited, or an empty string if the item
+    /// credits no narrator; see [`AudioBook::narrators`] for every narrator.
     pub fn narrator(&self) -> &str {
-        &self.narrator
+        self.narrators.first().map_or("", Narrator::name)
     }
 
     pub fn language(&self) -> &str {
@@ -80,7 +235,41 @@ impl AudioBook {
         self.release_date
     }
 
-    pub fn sample_url(&self) -> &Url {
-        &self.sample_url
+    pub fn series(&self) -> &[Series] {
+        &self.series
+    }
+
+    pub fn runtime(&self) -> Option<Duration> {
+        self.runtime
+    }
+
+    pub fn rating(&self) -> Option<Rating> {
+        self.rating
+    }
+
+    pub fn prices(&self) -> &Prices {
+        &self.prices
+    }
+
+    pub fn covers(&self) -> &[CoverImage] {
+        &self.covers
+    }
+
+    pub fn sample_url(&self) -> Option<&Url> {
+        self.sample_url.as_ref()
+    }
+}
+
+impl PartialEq for AudioBook {
+    fn eq(&self, other: &Self) -> bool {
+        self.asin == other.asin
+    }
+}
+
+impl Eq for AudioBook {}
+
+impl Hash for AudioBook {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.asin.hash(state);
     }
 }
@@ -0,0 +1,57 @@
+use serde::Serialize;
+use url::Url;
+
+/// An author credited on an audio book.
+///
+/// # Fields
+///
+/// * `name` - The name of the author as shown by Audible, e.g. "Pontus Sánchez Giménez - translator".
+/// * `url` - The Audible author page, or the author search when Audible has no page for them.
+/// * `id` - The Audible author ID (ASIN) taken from the author page URL, if available.
+///
+/// # Examples
+///
+/// ```
+/// use url::Url;
+/// use audible_scraper::Author;
+///
+/// let url = Url::parse("https://www.audible.es/author/Niklas-Natt-och-Dag/B07NQHVVFZ").unwrap();
+/// let author = Author::new(String::from("Niklas Natt och Dag"), Some(url));
+///
+/// assert_eq!(author.name(), "Niklas Natt och Dag");
+/// assert_eq!(author.id(), Some("B07NQHVVFZ"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Author {
+    name: String,
+    url: Option<Url>,
+    id: Option<String>,
+}
+
+impl Author {
+    /// Creates a new author, taking the ID from the URL when it points to an author page.
+    pub fn new(name: String, url: Option<Url>) -> Self {
+        let id = url.as_ref().and_then(|url| {
+            let mut segments = url.path_segments()?;
+            match (segments.next(), segments.next_back()) {
+                (Some("author"), Some(id)) if !id.is_empty() => Some(id.to_string()),
+                _ => None,
+            }
+        });
+
+        Self { name, url, id }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn url(&self) -> Option<&Url> {
+        self.url.as_ref()
+    }
+
+    pub fn id(&self) -> Option<&str> {
+        self.id.as_deref()
+    }
+}
@@ -1,7 +1,8 @@
 use reqwest::{header, Client};
-use std::error::Error;
+use url::Url;
 
-use crate::{QueryParams, Scraper};
+use crate::scraper::DEFAULT_CONCURRENCY;
+use crate::{Marketplace, QueryParams, RateLimiter, Result, RetryPolicy, Scraper};
 
 /// `Builder` is a struct that helps in constructing a `Scraper` instance with specified
 /// `QueryParams` and an optional `reqwest::Client`.
@@ -10,19 +11,43 @@ use crate::{QueryParams, Scraper};
 ///
 /// * `params` - The query parameters used for the scraper.
 /// * `client` - An optional HTTP client to be used by the scraper.
+/// * `concurrency` - The number of pages fetched at the same time, 4 by default.
+/// * `retry_policy` - How the requests failing with a transient error are retried.
+/// * `rate_limiter` - An optional limiter of the rate of the requests, none by default.
 ///
 /// # Methods
 ///
 /// * `new(params: QueryParams) -> Self` - Creates a new `Builder` instance with the given query parameters.
 /// * `params(&mut self, params: QueryParams) -> &mut Self` - Sets the query parameters for the builder.
+/// * `marketplace(&mut self, marketplace: Marketplace) -> &mut Self` - Sets the storefront to scrape.
 /// * `client(&mut self, client: Client) -> &mut Self` - Sets the HTTP client for the builder.
-/// * `build(&self) -> Result<Scraper, Box<dyn Error>>` - Constructs a `Scraper` instance using the specified parameters and client.
-
-#[derive(Default)]
+/// * `base_url(&mut self, base_url: Url) -> &mut Self` - Sends the requests to another server than the marketplace.
+/// * `concurrency(&mut self, concurrency: usize) -> &mut Self` - Sets the number of pages fetched at the same time.
+/// * `retry_policy(&mut self, retry_policy: RetryPolicy) -> &mut Self` - Sets how failed requests are retried.
+/// * `rate_limiter(&mut self, rate_limiter: RateLimiter) -> &mut Self` - Limits the rate of the requests.
+/// * `build(&self) -> Result<Scraper>` - Constructs a `Scraper` instance using the specified parameters and client.
 pub struct Builder {
     params: QueryParams,
     client: Option<Client>,
+    base_url: Option<Url>,
+    concurrency: usize,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            params: QueryParams::default(),
+            client: None,
+            base_url: None,
+            concurrency: DEFAULT_CONCURRENCY,
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: None,
+        }
+    }
 }
+
 impl Builder {
     pub fn new(params: QueryParams) -> Self {
         Self {
@@ -46,19 +71,60 @@ impl Builder {
         self
     }
 
+    /// Override the storefront of the query parameters.
+    ///
+    /// The marketplace also selects the default `Accept-Language` header of the HTTP client,
+    /// unless a custom client is provided.
+    pub fn marketplace(&mut self, marketplace: Marketplace) -> &mut Self {
+        self.params = self.params.clone().marketplace(marketplace);
+        self
+    }
+
     pub fn client(&mut self, client: Client) -> &mut Self {
         self.client = Some(client);
         self
     }
 
-    pub fn build(&self) -> Result<Scraper, Box<dyn Error>> {
+    /// Sends the requests to another server, e.g. a caching proxy or a test server, instead
+    /// of the host of the marketplace. The pages are still parsed as served by the marketplace.
+    pub fn base_url(&mut self, base_url: Url) -> &mut Self {
+        self.base_url = Some(base_url);
+        self
+    }
+
+    /// Sets the number of pages fetched at the same time once the first page tells how many
+    /// pages the search has. `1` fetches the pages one after the other, `0` is treated as `1`.
+    ///
+    /// The pages are returned in page order whatever the concurrency.
+    pub fn concurrency(&mut self, concurrency: usize) -> &mut Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Sets how the requests failing with a transient error, such as a `503` or a `429`, are
+    /// retried. See [`RetryPolicy`] for the defaults; use [`RetryPolicy::never`] to disable
+    /// the retries.
+    pub fn retry_policy(&mut self, retry_policy: RetryPolicy) -> &mut Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Limits the rate of the requests of the scraper, retries included. Give clones of the
+    /// same [`RateLimiter`] to several builders to limit the requests of all their scrapers
+    /// together, e.g. when they share one client.
+    pub fn rate_limiter(&mut self, rate_limiter: RateLimiter) -> &mut Self {
+        self.rate_limiter = Some(rate_limiter);
+        self
+    }
+
+    pub fn build(&self) -> Result<Scraper> {
         let client = self.client.clone().unwrap_or(Client::builder()
         .default_headers({
             let mut headers = header::HeaderMap::new();
             headers.insert(header::ACCEPT, header::HeaderValue::from_static(
                 "text/html,application/xhtml+xml,application/xml;q=0.9,image/avif,image/webp,image/apng,*/*;q=0.8,application/signed-exchange;v=b3;q=0.7"
             ));
-            headers.insert(header::ACCEPT_LANGUAGE, header::HeaderValue::from_static("es-ES,es;q=0.9,en;q=0.8"));
+            headers.insert(header::ACCEPT_LANGUAGE, header::HeaderValue::from_static(self.params.get_marketplace().accept_language()));
             headers.insert(header::CACHE_CONTROL, header::HeaderValue::from_static("max-age=0"));
             headers.insert(header::USER_AGENT, header::HeaderValue::from_static(
                 "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/93.0.4577.82 Safari/537.36"
@@ -67,6 +133,10 @@ impl Builder {
             headers
         }).build()?);
 
-        Ok(Scraper::new(client, self.params.clone()))
+        Ok(Scraper::new(client, self.params.clone())
+            .with_base_url(self.base_url.clone())
+            .with_concurrency(self.concurrency)
+            .with_retry_policy(self.retry_policy.clone())
+            .with_rate_limiter(self.rate_limiter.clone()))
     }
 }
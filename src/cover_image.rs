@@ -0,0 +1,51 @@
+use regex::Regex;
+use serde::Serialize;
+use std::sync::LazyLock;
+use url::Url;
+
+// `https://m.media-amazon.com/images/I/51iqmOAk5cL._SL500_.jpg`
+static SIZE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\._S[LXYS](\d+)_\.").unwrap());
+
+/// A cover image of an audio book, at one of the sizes served by Audible.
+///
+/// # Fields
+///
+/// * `url` - The URL of the image.
+/// * `size` - The size in pixels of the longest side of the image, if known.
+///
+/// # Examples
+///
+/// ```
+/// use url::Url;
+/// use audible_scraper::CoverImage;
+///
+/// let url = Url::parse("https://m.media-amazon.com/images/I/51iqmOAk5cL._SL500_.jpg").unwrap();
+/// let cover = CoverImage::new(url);
+///
+/// assert_eq!(cover.size(), Some(500));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CoverImage {
+    url: Url,
+    size: Option<u32>,
+}
+
+impl CoverImage {
+    /// Creates a new cover image, taking the size from the URL (e.g. `._SL500_.`).
+    pub fn new(url: Url) -> Self {
+        let size = SIZE
+            .captures(url.path())
+            .and_then(|caps| caps[1].parse().ok());
+
+        Self { url, size }
+    }
+
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    pub fn size(&self) -> Option<u32> {
+        self.size
+    }
+}
@@ -0,0 +1,113 @@
+use serde::Serialize;
+use std::{error::Error, fmt};
+
+/// A problem found while extracting a single item of a results page.
+///
+/// Diagnostics are collected by [`PageResult::collect_lenient`](crate::PageResult::collect_lenient)
+/// so that one malformed item does not discard the rest of the page.
+///
+/// # Fields
+///
+/// * `index` - The position of the item in the page, starting at 0.
+/// * `asin` - The ASIN of the item, if it could be read.
+/// * `field` - The field that is missing or could not be parsed, e.g. `"narrators"`.
+/// * `message` - A description of the problem.
+/// * `snippet` - The raw HTML the field was read from, truncated.
+/// * `skipped` - Whether the item was left out of the results, or kept without the field.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Diagnostic {
+    index: usize,
+    asin: Option<String>,
+    field: &'static str,
+    message: String,
+    snippet: String,
+    skipped: bool,
+}
+
+impl Diagnostic {
+    pub(crate) fn new(
+        index: usize,
+        asin: Option<String>,
+        field: &'static str,
+        message: impl Into<String>,
+        snippet: String,
+        skipped: bool,
+    ) -> Self {
+        Self {
+            index,
+            asin,
+            field,
+            message: message.into(),
+            snippet,
+            skipped,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn asin(&self) -> Option<&str> {
+        self.asin.as_deref()
+    }
+
+    pub fn field(&self) -> &str {
+        self.field
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn snippet(&self) -> &str {
+        &self.snippet
+    }
+
+    pub fn skipped(&self) -> bool {
+        self.skipped
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "item {}", self.index)?;
+        if let Some(asin) = &self.asin {
+            write!(f, " ({asin})")?;
+        }
+        write!(f, ": {}", self.message)?;
+        if self.skipped {
+            f.write_str(", item skipped")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for Diagnostic {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostic_display() {
+        let diagnostic = Diagnostic::new(
+            3,
+            Some(String::from("B0CHRR3BLS")),
+            "narrators",
+            "Narrator not found",
+            String::new(),
+            false,
+        );
+        assert_eq!(
+            diagnostic.to_string(),
+            "item 3 (B0CHRR3BLS): Narrator not found"
+        );
+
+        let diagnostic = Diagnostic::new(7, None, "asin", "ASIN not found", String::new(), true);
+        assert_eq!(
+            diagnostic.to_string(),
+            "item 7: ASIN not found, item skipped"
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use reqwest::StatusCode;
+use std::fmt;
+use std::time::Duration;
+use url::Url;
+
+use crate::{DateError, Diagnostic, Marketplace};
+
+/// A `Result` alias where the error is the crate [`Error`].
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+/// The error returned by the public API of the crate.
+///
+/// It is `Send + Sync + 'static`, so the futures of the [`Scraper`](crate::Scraper) can be
+/// spawned on a multithreaded runtime, and its variants let callers tell a network failure
+/// from a change in the layout of the pages.
+#[derive(Debug)]
+pub enum Error {
+    /// The request could not be sent or its response could not be read.
+    Transport(reqwest::Error),
+    /// The server answered with a status code other than success, after any retries;
+    /// `retry_after` is the delay asked by its `Retry-After` header, if any.
+    Status {
+        url: Url,
+        status: StatusCode,
+        retry_after: Option<Duration>,
+    },
+    /// Audible answered with a captcha or robot check instead of the requested page.
+    Blocked { url: Url },
+    /// The page neither lists results nor reports an empty search, e.g. after a redesign of
+    /// the search pages.
+    UnexpectedLayout { url: Url },
+    /// A page could not be parsed; `field` names what was being extracted.
+    Parse { field: String, context: String },
+    /// A URL could not be built or parsed.
+    Url(url::ParseError),
+    /// The page size is not one of the values Audible offers, see [`Marketplace::PAGE_SIZES`](crate::Marketplace::PAGE_SIZES).
+    InvalidPageSize {
+        page_size: u32,
+        allowed: &'static [u32],
+    },
+    /// Audible served a different number of results per page than requested.
+    PageSizeMismatch {
+        url: Url,
+        requested: u32,
+        served: u32,
+    },
+    /// A typed search filter matches none of the refinements offered on the marketplace.
+    UnsupportedFilter {
+        filter: &'static str,
+        marketplace: Marketplace,
+    },
+    /// A date could not be extracted from a text.
+    Date(DateError),
+}
+
+impl Error {
+    pub(crate) fn parse(field: impl Into<String>, context: impl Into<String>) -> Self {
+        Error::Parse {
+            field: field.into(),
+            context: context.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Transport(err) => write!(f, "Request failed: {err}"),
+            Error::Status { url, status, .. } => write!(f, "Unexpected status {status} for {url}"),
+            Error::Blocked { url } => write!(f, "Blocked by a captcha or robot check at {url}"),
+            Error::UnexpectedLayout { url } => {
+                write!(
+                    f,
+                    "Unexpected layout of the page at {url}, no results nor empty search found"
+                )
+            }
+            Error::Parse { field, context } => write!(f, "Cannot parse {field}: {context}"),
+            Error::Url(err) => write!(f, "Invalid URL: {err}"),
+            Error::InvalidPageSize { page_size, allowed } => {
+                let allowed: Vec<String> = allowed.iter().map(u32::to_string).collect();
+                write!(
+                    f,
+                    "Invalid page size {page_size}, expected one of {}",
+                    allowed.join(", ")
+                )
+            }
+            Error::PageSizeMismatch {
+                url,
+                requested,
+                served,
+            } => write!(
+                f,
+                "Requested {requested} results per page but {served} were served at {url}"
+            ),
+            Error::UnsupportedFilter {
+                filter,
+                marketplace,
+            } => write!(
+                f,
+                "The {filter} filter is not offered by the search on the {marketplace} \
+                 marketplace, use a refinement from the search facets instead"
+            ),
+            Error::Date(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Transport(err) => Some(err),
+            Error::Url(err) => Some(err),
+            Error::Date(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Error::Transport(err)
+    }
+}
+
+impl From<url::ParseError> for Error {
+    fn from(err: url::ParseError) -> Self {
+        Error::Url(err)
+    }
+}
+
+impl From<DateError> for Error {
+    fn from(err: DateError) -> Self {
+        Error::Date(err)
+    }
+}
+
+impl From<Diagnostic> for Error {
+    fn from(diagnostic: Diagnostic) -> Self {
+        Error::parse(diagnostic.field(), diagnostic.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Builder, QueryParams};
+
+    fn assert_send_sync<T: Send + Sync + 'static>(_: &T) {}
+
+    #[test]
+    fn test_error_is_send_sync() {
+        let err = Error::from(url::ParseError::EmptyHost);
+        assert_send_sync(&err);
+
+        let scraper = Builder::new(QueryParams::new()).build().unwrap();
+        let fetch_all = scraper.fetch_all();
+        fn assert_send<T: Send>(_: &T) {}
+        assert_send(&fetch_all);
+    }
+
+    #[test]
+    fn test_error_display() {
+        let url = Url::parse("https://www.audible.es/search").unwrap();
+        assert_eq!(
+            Error::Status {
+                url: url.clone(),
+                status: StatusCode::SERVICE_UNAVAILABLE,
+                retry_after: None,
+            }
+            .to_string(),
+            "Unexpected status 503 Service Unavailable for https://www.audible.es/search"
+        );
+        assert_eq!(
+            Error::Blocked { url: url.clone() }.to_string(),
+            "Blocked by a captcha or robot check at https://www.audible.es/search"
+        );
+        assert_eq!(
+            Error::UnexpectedLayout { url }.to_string(),
+            "Unexpected layout of the page at https://www.audible.es/search, no results nor empty search found"
+        );
+        assert_eq!(
+            Error::InvalidPageSize {
+                page_size: 25,
+                allowed: &[20, 30, 40, 50]
+            }
+            .to_string(),
+            "Invalid page size 25, expected one of 20, 30, 40, 50"
+        );
+        assert_eq!(
+            Error::parse("narrators", "Narrator not found").to_string(),
+            "Cannot parse narrators: Narrator not found"
+        );
+    }
+}
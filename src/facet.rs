@@ -0,0 +1,226 @@
+use regex::Regex;
+use serde::Serialize;
+use std::sync::LazyLock;
+use url::Url;
+
+use crate::{QueryParams, SortOrder};
+
+// `Ficción (45)`, `Español (1.234)`
+static LABEL_COUNT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^(.*?)\s*\((\d[\d.,]*)\)$").unwrap());
+
+/// A way of refining a search offered by the results page, e.g. the categories, the release
+/// date buckets, the lengths, the languages or the sort order.
+///
+/// # Fields
+///
+/// * `key` - The query parameter the facet sets, e.g. `node` or `feature_seven_browse-bin`.
+/// * `name` - The name of the facet as shown by Audible, e.g. "Duración".
+/// * `options` - The values the facet can take.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Facet {
+    key: String,
+    name: String,
+    options: Vec<FacetOption>,
+}
+
+impl Facet {
+    pub fn new(key: String, name: String, options: Vec<FacetOption>) -> Self {
+        Self { key, name, options }
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn options(&self) -> &[FacetOption] {
+        &self.options
+    }
+
+    /// Returns the option currently applied to the search, if any.
+    pub fn selected(&self) -> Option<&FacetOption> {
+        self.options.iter().find(|option| option.selected)
+    }
+}
+
+/// One of the values of a [`Facet`].
+///
+/// # Fields
+///
+/// * `key` - The query parameter the option sets, the same as its facet.
+/// * `value` - The value of the query parameter, e.g. `18385624031`.
+/// * `label` - The label shown by Audible, e.g. "Menos de 1 hora".
+/// * `count` - The number of results with this option, when the page shows it.
+/// * `selected` - Whether the option is applied to the current search.
+/// * `url` - The link of the results page with the option applied, if any.
+///
+/// # Examples
+///
+/// ```
+/// use audible_scraper::{FacetOption, QueryParams};
+///
+/// let option = FacetOption::new(
+///     "feature_six_browse-bin".to_string(),
+///     "18385686031".to_string(),
+///     "Español (45)",
+///     false,
+///     None,
+/// );
+/// assert_eq!(option.label(), "Español");
+/// assert_eq!(option.count(), Some(45));
+///
+/// let url = option.apply(QueryParams::new()).build_url().unwrap();
+/// assert!(url.as_str().contains("feature_six_browse-bin=18385686031"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FacetOption {
+    key: String,
+    value: String,
+    label: String,
+    count: Option<u32>,
+    selected: bool,
+    url: Option<Url>,
+}
+
+impl FacetOption {
+    /// Creates a new option, splitting a trailing count such as `(45)` from the label.
+    pub fn new(key: String, value: String, label: &str, selected: bool, url: Option<Url>) -> Self {
+        let label = label.split_whitespace().collect::<Vec<_>>().join(" ");
+        let (label, count) = match LABEL_COUNT.captures(&label) {
+            Some(caps) => (
+                caps[1].to_string(),
+                caps[2].replace(['.', ','], "").parse().ok(),
+            ),
+            None => (label, None),
+        };
+
+        Self {
+            key,
+            value,
+            label,
+            count,
+            selected,
+            url,
+        }
+    }
+
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn count(&self) -> Option<u32> {
+        self.count
+    }
+
+    pub fn selected(&self) -> bool {
+        self.selected
+    }
+
+    pub fn url(&self) -> Option<&Url> {
+        self.url.as_ref()
+    }
+
+    /// Returns `params` with this option applied.
+    ///
+    /// The category, sort and page size options set the corresponding parameters, an empty
+    /// page size being Audible's default of 20, and any other option is added as a refinement
+    /// replacing the typed filter with the same key, if any. A sort order unknown to
+    /// [`SortOrder`] leaves `params` unchanged.
+    pub fn apply(&self, params: QueryParams) -> QueryParams {
+        match self.key.as_str() {
+            "node" => params.category(&self.value),
+            "sort" => match self.value.parse::<SortOrder>() {
+                Ok(sort) => params.sort(sort),
+                Err(_) => params,
+            },
+            "pageSize" => params.page_size(self.value.parse().unwrap_or(20)),
+            key => params.refinement(key, &self.value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Length;
+
+    fn option(key: &str, value: &str, label: &str) -> FacetOption {
+        FacetOption::new(key.to_string(), value.to_string(), label, false, None)
+    }
+
+    #[test]
+    fn test_facet_option_label_count() {
+        let teens = option("node", "18059300031", "\n  Adolescentes\n");
+        assert_eq!(teens.label(), "Adolescentes");
+        assert_eq!(teens.count(), None);
+
+        let fiction = option("node", "18059290031", "Ficción (1.234)");
+        assert_eq!(fiction.label(), "Ficción");
+        assert_eq!(fiction.count(), Some(1234));
+    }
+
+    #[test]
+    fn test_facet_option_apply() {
+        let params = QueryParams::new().narrator("Jordi Salas");
+
+        let url = option("sort", "popularity-rank", "Popular")
+            .apply(params.clone())
+            .build_url()
+            .unwrap();
+        assert!(url.as_str().contains("sort=popularity-rank"));
+
+        let url = option("pageSize", "", "20")
+            .apply(params.clone())
+            .build_url()
+            .unwrap();
+        assert!(url.as_str().contains("pageSize=20"));
+
+        let url = option("node", "18059300031", "Adolescentes")
+            .apply(params.clone())
+            .build_url()
+            .unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://www.audible.es/search?searchNarrator=Jordi+Salas&node=18059300031&sort=title-asc-rank&pageSize=50&page=1"
+        );
+    }
+
+    /// Applying an option replaces the typed filter with the same key rather than repeating it.
+    #[test]
+    fn test_facet_option_apply_replaces_filters() {
+        let params = QueryParams::new()
+            .narrator("Jordi Salas")
+            .category("18059298031")
+            .length(Length::OverTwentyHours);
+
+        let url = option("node", "18059300031", "Adolescentes")
+            .apply(params.clone())
+            .build_url()
+            .unwrap();
+        assert_eq!(url.as_str().matches("node=").count(), 1);
+        assert!(url.as_str().contains("node=18059300031"));
+
+        let url = option(Length::KEY, "18385624031", "Menos de 1 hora")
+            .apply(params)
+            .build_url()
+            .unwrap();
+        assert_eq!(url.as_str().matches(Length::KEY).count(), 1);
+        assert!(url
+            .as_str()
+            .contains("feature_seven_browse-bin=18385624031"));
+    }
+}
@@ -0,0 +1,474 @@
+//! Typed filters of a search, set with [`QueryParams`](crate::QueryParams).
+//!
+//! The refinement ids of the filters are specific to each marketplace. They are known for some
+//! marketplaces, see e.g. [`Language::id`], and otherwise found by label in the facets of the
+//! search without the filters, see e.g. [`Language::find`], which takes one more request. The ids
+//! found that way are remembered, so the later searches on the same marketplace skip it.
+//!
+//! There is no price filter: the search page offers no price refinement, only the
+//! [`SortOrder::PriceAsc`](crate::SortOrder::PriceAsc) and
+//! [`SortOrder::PriceDesc`](crate::SortOrder::PriceDesc) orders. Restrict the prices on the
+//! scraped audiobooks instead, see [`AudioBook::prices`](crate::AudioBook::prices).
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+
+use crate::{Facet, FacetOption, Marketplace};
+
+// `Últimos 30 días`, `1–3 horas`, `Less than 1 hour`, `20時間以上`
+static LABEL_NUMBER: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d+").unwrap());
+
+// Refinement ids found in the facets of earlier searches
+static FOUND_IDS: LazyLock<Mutex<HashMap<(Marketplace, Filter), String>>> =
+    LazyLock::new(Default::default);
+
+/// Restricts a search to the audiobooks released in a recent period (`publication_date`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum ReleasedWithin {
+    /// Released in the last 30 days.
+    #[serde(rename = "last-30-days")]
+    #[cfg_attr(feature = "cli", value(name = "last-30-days"))]
+    Last30Days,
+    /// Released in the last 90 days.
+    #[serde(rename = "last-90-days")]
+    #[cfg_attr(feature = "cli", value(name = "last-90-days"))]
+    Last90Days,
+}
+
+impl ReleasedWithin {
+    /// Query parameter of the filter.
+    pub const KEY: &'static str = "publication_date";
+
+    pub const ALL: [ReleasedWithin; 2] = [ReleasedWithin::Last30Days, ReleasedWithin::Last90Days];
+
+    /// Refinement id of the filter on `marketplace`, if known.
+    pub fn id(&self, marketplace: Marketplace) -> Option<&'static str> {
+        match (marketplace, self) {
+            (Marketplace::Es, ReleasedWithin::Last30Days) => Some("18385621031"),
+            (Marketplace::Es, ReleasedWithin::Last90Days) => Some("18385622031"),
+            _ => None,
+        }
+    }
+
+    /// Returns the filter with the refinement id `id` on `marketplace`, if any.
+    pub fn from_id(id: &str, marketplace: Marketplace) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|filter| filter.id(marketplace) == Some(id))
+    }
+
+    /// Returns the option of `facets` applying the filter, recognised by the number of days
+    /// in its label, e.g. "Last 30 days" or "Últimos 30 días".
+    pub fn find<'a>(&self, facets: &'a [Facet]) -> Option<&'a FacetOption> {
+        let days = match self {
+            ReleasedWithin::Last30Days => [30],
+            ReleasedWithin::Last90Days => [90],
+        };
+        find_option(facets, Self::KEY, |option| label_numbers(option) == days)
+    }
+}
+
+/// Restricts a search to the audiobooks of a length bucket (`feature_seven_browse-bin`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Length {
+    /// Less than 1 hour.
+    #[serde(rename = "under-1h")]
+    #[cfg_attr(feature = "cli", value(name = "under-1h"))]
+    UnderOneHour,
+    /// From 1 to 3 hours.
+    #[serde(rename = "1-3h")]
+    #[cfg_attr(feature = "cli", value(name = "1-3h"))]
+    OneToThreeHours,
+    /// From 3 to 6 hours.
+    #[serde(rename = "3-6h")]
+    #[cfg_attr(feature = "cli", value(name = "3-6h"))]
+    ThreeToSixHours,
+    /// From 6 to 10 hours.
+    #[serde(rename = "6-10h")]
+    #[cfg_attr(feature = "cli", value(name = "6-10h"))]
+    SixToTenHours,
+    /// From 10 to 20 hours.
+    #[serde(rename = "10-20h")]
+    #[cfg_attr(feature = "cli", value(name = "10-20h"))]
+    TenToTwentyHours,
+    /// More than 20 hours.
+    #[serde(rename = "over-20h")]
+    #[cfg_attr(feature = "cli", value(name = "over-20h"))]
+    OverTwentyHours,
+}
+
+impl Length {
+    /// Query parameter of the filter.
+    pub const KEY: &'static str = "feature_seven_browse-bin";
+
+    pub const ALL: [Length; 6] = [
+        Length::UnderOneHour,
+        Length::OneToThreeHours,
+        Length::ThreeToSixHours,
+        Length::SixToTenHours,
+        Length::TenToTwentyHours,
+        Length::OverTwentyHours,
+    ];
+
+    /// Refinement id of the filter on `marketplace`, if known.
+    pub fn id(&self, marketplace: Marketplace) -> Option<&'static str> {
+        match (marketplace, self) {
+            (Marketplace::Es, Length::UnderOneHour) => Some("18385624031"),
+            (Marketplace::Es, Length::OneToThreeHours) => Some("18385625031"),
+            (Marketplace::Es, Length::ThreeToSixHours) => Some("18385626031"),
+            (Marketplace::Es, Length::SixToTenHours) => Some("18385627031"),
+            (Marketplace::Es, Length::TenToTwentyHours) => Some("18385628031"),
+            (Marketplace::Es, Length::OverTwentyHours) => Some("18385629031"),
+            _ => None,
+        }
+    }
+
+    /// Returns the filter with the refinement id `id` on `marketplace`, if any.
+    pub fn from_id(id: &str, marketplace: Marketplace) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|filter| filter.id(marketplace) == Some(id))
+    }
+
+    /// Returns the option of `facets` applying the filter, recognised by the hours in its
+    /// label, e.g. "1 to 3 hours" or "1–3 horas"; the open-ended buckets only have one number,
+    /// e.g. "Less than 1 hour" and "Over 20 hours".
+    pub fn find<'a>(&self, facets: &'a [Facet]) -> Option<&'a FacetOption> {
+        let hours: &[u32] = match self {
+            Length::UnderOneHour => &[1],
+            Length::OneToThreeHours => &[1, 3],
+            Length::ThreeToSixHours => &[3, 6],
+            Length::SixToTenHours => &[6, 10],
+            Length::TenToTwentyHours => &[10, 20],
+            Length::OverTwentyHours => &[20],
+        };
+        find_option(facets, Self::KEY, |option| label_numbers(option) == hours)
+    }
+}
+
+/// Restricts a search to the audiobooks narrated in a language (`feature_six_browse-bin`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum Language {
+    English,
+    Spanish,
+    Catalan,
+    German,
+    French,
+    Italian,
+    Portuguese,
+    Japanese,
+}
+
+impl Language {
+    /// Query parameter of the filter.
+    pub const KEY: &'static str = "feature_six_browse-bin";
+
+    pub const ALL: [Language; 8] = [
+        Language::English,
+        Language::Spanish,
+        Language::Catalan,
+        Language::German,
+        Language::French,
+        Language::Italian,
+        Language::Portuguese,
+        Language::Japanese,
+    ];
+
+    /// Refinement id of the filter on `marketplace`, if known.
+    pub fn id(&self, marketplace: Marketplace) -> Option<&'static str> {
+        match (marketplace, self) {
+            (Marketplace::Es, Language::Spanish) => Some("18385686031"),
+            (Marketplace::Es, Language::Catalan) => Some("18385713031"),
+            _ => None,
+        }
+    }
+
+    /// Returns the filter with the refinement id `id` on `marketplace`, if any.
+    pub fn from_id(id: &str, marketplace: Marketplace) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|filter| filter.id(marketplace) == Some(id))
+    }
+
+    /// Names of the language in the languages of the marketplaces, lowercased.
+    fn names(&self) -> &'static [&'static str] {
+        match self {
+            Language::English => &[
+                "english", "inglés", "englisch", "anglais", "inglese", "inglês", "英語",
+            ],
+            Language::Spanish => &[
+                "spanish",
+                "español",
+                "spanisch",
+                "espagnol",
+                "spagnolo",
+                "espanhol",
+                "スペイン語",
+            ],
+            Language::Catalan => &[
+                "catalan",
+                "catalán",
+                "katalanisch",
+                "catalano",
+                "catalão",
+                "català",
+                "カタロニア語",
+            ],
+            Language::German => &[
+                "german",
+                "alemán",
+                "deutsch",
+                "allemand",
+                "tedesco",
+                "alemão",
+                "ドイツ語",
+            ],
+            Language::French => &[
+                "french",
+                "francés",
+                "französisch",
+                "français",
+                "francese",
+                "francês",
+                "フランス語",
+            ],
+            Language::Italian => &[
+                "italian",
+                "italiano",
+                "italienisch",
+                "italien",
+                "イタリア語",
+            ],
+            Language::Portuguese => &[
+                "portuguese",
+                "portugués",
+                "portugiesisch",
+                "portugais",
+                "portoghese",
+                "português",
+                "ポルトガル語",
+            ],
+            Language::Japanese => &[
+                "japanese",
+                "japonés",
+                "japanisch",
+                "japonais",
+                "giapponese",
+                "japonês",
+                "日本語",
+            ],
+        }
+    }
+
+    /// Returns the option of `facets` applying the filter, recognised by the name of the
+    /// language in its label, e.g. "English" or "Español".
+    pub fn find<'a>(&self, facets: &'a [Facet]) -> Option<&'a FacetOption> {
+        find_option(facets, Self::KEY, |option| {
+            let label = option.label().to_lowercase();
+            // `English (US)`
+            let name = label.split(" (").next().unwrap_or_default().trim();
+            self.names().contains(&name)
+        })
+    }
+}
+
+/// Any of the typed filters, to remember the refinement ids found in the facets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum Filter {
+    ReleasedWithin(ReleasedWithin),
+    Language(Language),
+    Length(Length),
+}
+
+impl Filter {
+    /// Name of the filter in the errors.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            Filter::ReleasedWithin(_) => "released within",
+            Filter::Language(_) => "language",
+            Filter::Length(_) => "length",
+        }
+    }
+
+    /// Returns the refinement id of the filter on `marketplace` found in an earlier search.
+    pub(crate) fn found_id(self, marketplace: Marketplace) -> Option<String> {
+        let found = FOUND_IDS.lock().unwrap();
+        found.get(&(marketplace, self)).cloned()
+    }
+
+    /// Remembers `id` as the refinement id of the filter on `marketplace`.
+    pub(crate) fn remember(self, marketplace: Marketplace, id: &str) {
+        let mut found = FOUND_IDS.lock().unwrap();
+        found.insert((marketplace, self), id.to_string());
+    }
+}
+
+/// Returns the first option of the facet with the query parameter `key` matching `predicate`.
+fn find_option<'a>(
+    facets: &'a [Facet],
+    key: &str,
+    predicate: impl Fn(&FacetOption) -> bool,
+) -> Option<&'a FacetOption> {
+    facets
+        .iter()
+        .filter(|facet| facet.key() == key)
+        .flat_map(Facet::options)
+        .find(|option| predicate(option))
+}
+
+/// Returns the numbers in the label of `option`, e.g. `[1, 3]` for "1–3 horas".
+fn label_numbers(option: &FacetOption) -> Vec<u32> {
+    LABEL_NUMBER
+        .find_iter(option.label())
+        .filter_map(|number| number.as_str().parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page_result::tests::first_page;
+
+    fn value(option: Option<&FacetOption>) -> Option<&str> {
+        option.map(FacetOption::value)
+    }
+
+    fn fixture_facets() -> Vec<Facet> {
+        first_page().facets()
+    }
+
+    /// The ids of the filters must match the refinements offered by the search page.
+    #[test]
+    fn test_filter_ids_match_facets() {
+        let facets = fixture_facets();
+        let ids = |key: &str| -> Vec<String> {
+            facets
+                .iter()
+                .find(|facet| facet.key() == key)
+                .map(Facet::options)
+                .unwrap_or_default()
+                .iter()
+                .map(FacetOption::value)
+                .map(str::to_string)
+                .collect()
+        };
+
+        let released_within = ReleasedWithin::ALL.map(|filter| filter.id(Marketplace::Es).unwrap());
+        assert_eq!(ids(ReleasedWithin::KEY), released_within);
+
+        let lengths = Length::ALL.map(|filter| filter.id(Marketplace::Es).unwrap());
+        assert_eq!(ids(Length::KEY), lengths);
+
+        let languages: Vec<&str> = Language::ALL
+            .iter()
+            .filter_map(|filter| filter.id(Marketplace::Es))
+            .collect();
+        assert_eq!(ids(Language::KEY), languages);
+    }
+
+    /// Files and the command line must use the same names for the filters.
+    #[cfg(feature = "cli")]
+    #[test]
+    fn test_filter_names() {
+        use clap::ValueEnum;
+
+        fn assert_names<T: ValueEnum + Serialize>(filters: &[T]) {
+            for filter in filters {
+                let name = filter.to_possible_value().unwrap().get_name().to_string();
+                assert_eq!(
+                    serde_json::to_string(filter).unwrap(),
+                    format!("\"{name}\"")
+                );
+            }
+        }
+
+        assert_names(ReleasedWithin::value_variants());
+        assert_names(Length::value_variants());
+        assert_names(Language::value_variants());
+    }
+
+    #[test]
+    fn test_filter_from_id() {
+        for filter in Length::ALL {
+            let id = filter.id(Marketplace::Es).unwrap();
+            assert_eq!(Length::from_id(id, Marketplace::Es), Some(filter));
+        }
+        assert_eq!(
+            Language::from_id("18385713031", Marketplace::Es),
+            Some(Language::Catalan)
+        );
+        assert_eq!(
+            ReleasedWithin::from_id("18385621031", Marketplace::Us),
+            None
+        );
+    }
+
+    #[test]
+    fn test_filter_ids_unknown_marketplace() {
+        assert_eq!(ReleasedWithin::Last30Days.id(Marketplace::Us), None);
+        assert_eq!(Length::UnderOneHour.id(Marketplace::De), None);
+        assert_eq!(Language::Spanish.id(Marketplace::Uk), None);
+    }
+
+    /// The options found in the facets must be those of the known ids.
+    #[test]
+    fn test_filter_find() {
+        let facets = fixture_facets();
+
+        for filter in ReleasedWithin::ALL {
+            assert_eq!(value(filter.find(&facets)), filter.id(Marketplace::Es));
+        }
+        for filter in Length::ALL {
+            assert_eq!(value(filter.find(&facets)), filter.id(Marketplace::Es));
+        }
+        for filter in Language::ALL {
+            assert_eq!(value(filter.find(&facets)), filter.id(Marketplace::Es));
+        }
+    }
+
+    #[test]
+    fn test_filter_find_english_labels() {
+        let facet = |key: &str, labels: &[&str]| {
+            let options = labels
+                .iter()
+                .enumerate()
+                .map(|(i, label)| {
+                    FacetOption::new(key.to_string(), i.to_string(), label, false, None)
+                })
+                .collect();
+            Facet::new(key.to_string(), String::new(), options)
+        };
+        let facets = [
+            facet(
+                ReleasedWithin::KEY,
+                &["Last 30 days (12)", "Last 90 days (40)"],
+            ),
+            facet(
+                Length::KEY,
+                &[
+                    "Less than 1 hour",
+                    "1 to 3 hours",
+                    "3 to 6 hours",
+                    "6 to 10 hours",
+                    "10 to 20 hours",
+                    "Over 20 hours",
+                ],
+            ),
+            facet(Language::KEY, &["English (US)", "Spanish", "German"]),
+        ];
+
+        assert_eq!(value(ReleasedWithin::Last90Days.find(&facets)), Some("1"));
+        assert_eq!(value(Length::UnderOneHour.find(&facets)), Some("0"));
+        assert_eq!(value(Length::TenToTwentyHours.find(&facets)), Some("4"));
+        assert_eq!(value(Length::OverTwentyHours.find(&facets)), Some("5"));
+        assert_eq!(value(Language::English.find(&facets)), Some("0"));
+        assert_eq!(value(Language::German.find(&facets)), Some("2"));
+        assert_eq!(value(Language::Japanese.find(&facets)), None);
+    }
+}
@@ -1,13 +1,47 @@
 mod audio_book;
+mod author;
 mod builder;
+mod cover_image;
+mod diagnostic;
+mod error;
+mod facet;
+mod filters;
+mod marketplace;
+mod narrator;
+mod page_kind;
 mod page_result;
 mod params;
+mod price;
+mod product_kind;
+mod rate_limiter;
+mod rating;
+mod retry_policy;
 mod scraper;
+mod series;
+mod sort_order;
+#[cfg(test)]
+mod test_server;
 mod utils;
 
 pub use audio_book::AudioBook;
+pub use author::Author;
 pub use builder::Builder;
+pub use cover_image::CoverImage;
+pub use diagnostic::Diagnostic;
+pub use error::{Error, Result};
+pub use facet::{Facet, FacetOption};
+pub use filters::{Language, Length, ReleasedWithin};
+pub use marketplace::{DateOrder, Marketplace};
+pub use narrator::Narrator;
+pub use page_kind::PageKind;
 pub use page_result::PageResult;
 pub use params::QueryParams;
+pub use price::{Price, Prices};
+pub use product_kind::ProductKind;
+pub use rate_limiter::RateLimiter;
+pub use rating::Rating;
+pub use retry_policy::RetryPolicy;
 pub use scraper::Scraper;
+pub use series::Series;
+pub use sort_order::SortOrder;
 pub use utils::*;
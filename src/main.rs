@@ -1,40 +1,307 @@
-use audible_scraper::{AudioBook, Builder, QueryParams};
+use audible_scraper::{
+    format_runtime, AudioBook, Builder, Language, Length, Marketplace, PageKind, QueryParams,
+    RateLimiter, ReleasedWithin, RetryPolicy, SortOrder,
+};
+use chrono::NaiveDate;
 use clap::{Parser, ValueEnum};
-use csv;
-use serde_json;
-use std::io::Write;
-use toml;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::pin::pin;
+use url::Url;
 
 /// CLI to scrape Audible audiobooks
 #[derive(Parser)]
 struct Args {
+    /// TOML or JSON file of named searches to run, each with its own output (optional)
+    #[arg(
+        long,
+        conflicts_with_all = [
+            "url", "narrator", "keywords", "title", "author", "publisher", "marketplace",
+            "category", "released_within", "language", "length", "sort", "format",
+        ]
+    )]
+    profile: Option<PathBuf>,
+
+    /// Name of a search of the profile to run, may be repeated; all of them by default
+    #[arg(long = "search", requires = "profile")]
+    searches: Vec<String>,
+
+    /// Audible search URL to scrape as is, e.g. copied from the browser (optional)
+    #[arg(
+        short,
+        long,
+        conflicts_with_all = [
+            "narrator", "keywords", "title", "author", "publisher", "marketplace", "category",
+            "released_within", "language", "length", "sort",
+        ]
+    )]
+    url: Option<String>,
+
     /// Narrator name to filter audiobooks (optional)
     #[arg(short, long)]
     narrator: Option<String>,
 
+    /// Free text to search for (optional)
     #[arg(short, long)]
     keywords: Option<String>,
 
+    /// Title to filter audiobooks (optional)
+    #[arg(short, long)]
+    title: Option<String>,
+
+    /// Author name to filter audiobooks (optional)
+    #[arg(short, long)]
+    author: Option<String>,
+
+    /// Publisher name to filter audiobooks (optional)
+    #[arg(short, long)]
+    publisher: Option<String>,
+
+    /// Audible storefront to scrape
+    #[arg(short, long, value_enum, default_value = "es")]
+    marketplace: Marketplace,
+
+    /// Category to restrict the search to, by its browse node id (optional)
+    #[arg(long)]
+    category: Option<String>,
+
+    /// Period in which the audiobooks were released (optional)
+    #[arg(long, value_enum)]
+    released_within: Option<ReleasedWithin>,
+
+    /// Language in which the audiobooks are narrated (optional)
+    #[arg(long, value_enum)]
+    language: Option<Language>,
+
+    /// Length of the audiobooks (optional)
+    #[arg(long, value_enum)]
+    length: Option<Length>,
+
+    /// Order of the results
+    #[arg(short, long, value_enum, default_value = "title-asc-rank")]
+    sort: SortOrder,
+
     /// Output format: jsonl, csv, json, or toml
     #[arg(short, long, value_enum, default_value = "json")]
     format: OutputFormat,
+
+    /// Number of pages fetched at the same time
+    #[arg(short = 'j', long, default_value_t = 4)]
+    concurrency: usize,
+
+    /// Number of times a request failing with a transient error is sent, 1 to never retry
+    #[arg(long, default_value_t = 3)]
+    max_attempts: u32,
+
+    /// Largest number of requests per second, shared by the searches of a profile (optional)
+    #[arg(long, value_parser = parse_rate)]
+    rate: Option<f64>,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
+/// Parses a number of requests per second, which must be positive.
+fn parse_rate(text: &str) -> Result<f64, String> {
+    match text.parse::<f64>() {
+        Ok(rate) if rate.is_finite() && rate > 0.0 => Ok(rate),
+        _ => Err(format!(
+            "{text} is not a positive number of requests per second"
+        )),
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
 enum OutputFormat {
     Jsonl,
     Csv,
+    #[default]
     Json,
     Toml,
 }
 
-#[tokio::main]
-async fn main() {
-    let args = Args::parse();
-    let mut params = QueryParams::new();
+/// A file of named searches, run with `--profile`.
+#[derive(Deserialize)]
+struct Profile {
+    searches: Vec<ProfileSearch>,
+}
+
+/// A named search of a profile, with its own output format and destination.
+#[derive(Deserialize)]
+struct ProfileSearch {
+    name: String,
+    #[serde(default)]
+    format: OutputFormat,
+    /// File to write the audiobooks to, stdout if missing.
+    output: Option<PathBuf>,
+    #[serde(flatten)]
+    params: QueryParams,
+}
+
+/// The keys a search of a profile may have, see [`ProfileSearch`].
+const PROFILE_SEARCH_KEYS: [&str; 17] = [
+    "name",
+    "format",
+    "output",
+    "marketplace",
+    "keywords",
+    "title",
+    "author",
+    "narrator",
+    "publisher",
+    "category",
+    "releasedWithin",
+    "language",
+    "length",
+    "refinements",
+    "sort",
+    "pageSize",
+    "page",
+];
 
-    if args.narrator.is_none() && args.keywords.is_none() {
-        panic!("At least one of the optional arguments (narrator or keywords) must be provided");
+/// Loads a profile, as JSON if the file has a `.json` extension and as TOML otherwise.
+///
+/// Unknown keys of the searches, likely typos, are reported as warnings, and searches without
+/// any search term are rejected since they would scrape the whole storefront.
+fn load_profile(path: &Path) -> Result<Profile, Box<dyn Error>> {
+    let text = fs::read_to_string(path)?;
+    let value: serde_json::Value = if path
+        .extension()
+        .is_some_and(|extension| extension == "json")
+    {
+        serde_json::from_str(&text)?
+    } else {
+        toml::from_str(&text)?
+    };
+
+    for search in value["searches"].as_array().into_iter().flatten() {
+        let name = search["name"].as_str().unwrap_or_default();
+        for key in search
+            .as_object()
+            .into_iter()
+            .flat_map(|search| search.keys())
+        {
+            if !PROFILE_SEARCH_KEYS.contains(&key.as_str()) {
+                eprintln!("Warning: search {}: unknown key {}", name, key);
+            }
+        }
+    }
+
+    let profile: Profile = serde_json::from_value(value)?;
+    if let Some(search) = profile
+        .searches
+        .iter()
+        .find(|search| !search.params.has_search_terms())
+    {
+        return Err(format!(
+            "search {} has none of keywords, title, author, narrator or publisher",
+            search.name
+        )
+        .into());
+    }
+
+    Ok(profile)
+}
+
+/// Flat representation of an `AudioBook` for CSV, which cannot hold nested values.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CsvRecord<'a> {
+    asin: &'a str,
+    url: Option<&'a str>,
+    kind: String,
+    title: &'a str,
+    subtitle: Option<&'a str>,
+    authors: String,
+    narrators: String,
+    language: &'a str,
+    release_date: Option<NaiveDate>,
+    series: String,
+    runtime_seconds: Option<u64>,
+    runtime: Option<String>,
+    rating: Option<f32>,
+    rating_count: Option<u32>,
+    price: Option<String>,
+    sale_price: Option<String>,
+    member_price: Option<String>,
+    currency: Option<&'a str>,
+    credits: Option<u32>,
+    cover_url: Option<&'a str>,
+    sample_url: Option<&'a str>,
+}
+
+impl<'a> From<&'a AudioBook> for CsvRecord<'a> {
+    fn from(audiobook: &'a AudioBook) -> Self {
+        let prices = audiobook.prices();
+        Self {
+            asin: audiobook.asin(),
+            url: audiobook.url().map(|url| url.as_str()),
+            kind: audiobook.kind().to_string(),
+            title: audiobook.title(),
+            subtitle: audiobook.subtitle(),
+            authors: audiobook
+                .authors()
+                .iter()
+                .map(|author| author.name())
+                .collect::<Vec<_>>()
+                .join("; "),
+            narrators: audiobook
+                .narrators()
+                .iter()
+                .map(|narrator| narrator.name())
+                .collect::<Vec<_>>()
+                .join("; "),
+            language: audiobook.language(),
+            release_date: audiobook.release_date(),
+            series: audiobook
+                .series()
+                .iter()
+                .map(|series| match series.position() {
+                    Some(position) => format!("{} #{}", series.name(), position),
+                    None => series.name().to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("; "),
+            runtime_seconds: audiobook.runtime().map(|runtime| runtime.as_secs()),
+            runtime: audiobook.runtime().map(format_runtime),
+            rating: audiobook.rating().map(|rating| rating.average()),
+            rating_count: audiobook.rating().map(|rating| rating.count()),
+            price: prices.regular().map(|price| price.amount()),
+            sale_price: prices.sale().map(|price| price.amount()),
+            member_price: prices.member().map(|price| price.amount()),
+            currency: [prices.regular(), prices.sale(), prices.member()]
+                .into_iter()
+                .flatten()
+                .next()
+                .map(|price| price.currency()),
+            credits: prices.credits(),
+            cover_url: audiobook.covers().last().map(|cover| cover.url().as_str()),
+            sample_url: audiobook.sample_url().map(Url::as_str),
+        }
+    }
+}
+
+/// TOML documents must be a table, so the audiobooks are emitted as an array of tables.
+#[derive(Serialize)]
+struct TomlDocument<'a> {
+    audiobooks: &'a [AudioBook],
+}
+
+/// Builds the search described by the search and filter arguments.
+fn search_params(args: &Args) -> QueryParams {
+    let mut params = QueryParams::new()
+        .marketplace(args.marketplace)
+        .sort(args.sort);
+
+    if args.narrator.is_none()
+        && args.keywords.is_none()
+        && args.title.is_none()
+        && args.author.is_none()
+        && args.publisher.is_none()
+    {
+        panic!("At least one of the optional arguments (url, narrator, keywords, title, author or publisher) must be provided");
     }
 
     if let Some(narrator) = args.narrator.as_deref() {
@@ -45,51 +312,270 @@ async fn main() {
         params = params.keywords(keywords);
     }
 
-    let scraper = Builder::new(params)
-        .build()
-        .expect("Failed to build scraper");
-
-    match scraper.fetch_all().await {
-        Ok(result) => {
-            let audiobooks: Vec<AudioBook> =
-                result.iter().flat_map(|f| f.collect().unwrap()).collect();
-
-            // Handle output format based on the format parameter
-            match args.format {
-                OutputFormat::Jsonl => {
-                    for audiobook in &audiobooks {
-                        std::io::stdout()
-                            .write_all(serde_json::to_string(&audiobook).unwrap().as_bytes())
-                            .unwrap();
-                        std::io::stdout().write_all(b"\n").unwrap();
-                    }
-                }
-                OutputFormat::Csv => {
-                    let mut wtr = csv::Writer::from_writer(std::io::stdout());
-                    for audiobook in &audiobooks {
-                        wtr.serialize(audiobook).unwrap();
-                    }
-                    wtr.flush().unwrap();
+    if let Some(title) = args.title.as_deref() {
+        params = params.title(title);
+    }
+
+    if let Some(author) = args.author.as_deref() {
+        params = params.author(author);
+    }
+
+    if let Some(publisher) = args.publisher.as_deref() {
+        params = params.publisher(publisher);
+    }
+
+    if let Some(category) = args.category.as_deref() {
+        params = params.category(category);
+    }
+
+    if let Some(released_within) = args.released_within {
+        params = params.released_within(released_within);
+    }
+
+    if let Some(language) = args.language {
+        params = params.language(language);
+    }
+
+    if let Some(length) = args.length {
+        params = params.length(length);
+    }
+
+    params
+}
+
+/// Returns a builder of a scraper of the search, with the fetching options of the arguments.
+fn builder(args: &Args, params: QueryParams, rate_limiter: Option<&RateLimiter>) -> Builder {
+    let mut builder = Builder::new(params);
+    builder
+        .concurrency(args.concurrency)
+        .retry_policy(RetryPolicy::new().with_max_attempts(args.max_attempts));
+    if let Some(rate_limiter) = rate_limiter {
+        builder.rate_limiter(rate_limiter.clone());
+    }
+    builder
+}
+
+/// Writes the audiobooks to `out` in the given format.
+fn write_audiobooks(
+    audiobooks: &[AudioBook],
+    format: OutputFormat,
+    mut out: impl Write,
+) -> Result<(), Box<dyn Error>> {
+    match format {
+        OutputFormat::Jsonl => {
+            for audiobook in audiobooks {
+                serde_json::to_writer(&mut out, audiobook)?;
+                out.write_all(b"\n")?;
+            }
+        }
+        OutputFormat::Csv => {
+            let mut wtr = csv::Writer::from_writer(out);
+            for audiobook in audiobooks {
+                wtr.serialize(CsvRecord::from(audiobook))?;
+            }
+            wtr.flush()?;
+        }
+        OutputFormat::Json => serde_json::to_writer_pretty(&mut out, audiobooks)?,
+        OutputFormat::Toml => {
+            let toml_output = toml::to_string(&TomlDocument { audiobooks })?;
+            out.write_all(toml_output.as_bytes())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Scrapes every page of the search of `builder` and writes the audiobooks to `output`, or stdout if `None`.
+///
+/// JSONL is written page by page as they are fetched; the other formats are written once the
+/// last page is fetched. When a page fails, the audiobooks fetched so far are still written
+/// before the error is returned.
+async fn run(
+    builder: &Builder,
+    format: OutputFormat,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn Error>> {
+    let scraper = builder.build()?;
+    let mut out: Box<dyn Write> = match output {
+        Some(path) => Box::new(io::BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+
+    let mut pages = pin!(scraper.stream_pages());
+    let mut audiobooks: Vec<AudioBook> = Vec::new();
+    let mut collected = 0;
+    let mut total_results = None;
+    let mut error = None;
+    while let Some(page) = pages.next().await {
+        let page = match page {
+            Ok(page) => page,
+            Err(err) => {
+                error = Some(err);
+                break;
+            }
+        };
+        match page.total_pages() {
+            Some(total_pages) => eprintln!("Fetched page {} of {}", page.page(), total_pages),
+            None => eprintln!("Fetched page {}", page.page()),
+        }
+        total_results = total_results.or(page.total_results());
+        // An empty search is not a failure, its output is just empty
+        if page.kind() == PageKind::NoResults {
+            eprintln!("No results found");
+        }
+
+        match page.collect_lenient() {
+            Ok((page_audiobooks, diagnostics)) => {
+                for diagnostic in diagnostics {
+                    eprintln!("Warning: page {}, {}", page.page(), diagnostic);
                 }
-                OutputFormat::Json => {
-                    std::io::stdout()
-                        .write_all(
-                            serde_json::to_string_pretty(&audiobooks)
-                                .unwrap()
-                                .as_bytes(),
-                        )
-                        .unwrap();
+                collected += page_audiobooks.len();
+                if format == OutputFormat::Jsonl {
+                    write_audiobooks(&page_audiobooks, format, &mut out)?;
+                    out.flush()?;
+                } else {
+                    audiobooks.extend(page_audiobooks);
                 }
-                OutputFormat::Toml => match toml::to_string(&audiobooks) {
-                    Ok(toml_output) => {
-                        std::io::stdout().write_all(toml_output.as_bytes()).unwrap();
-                    }
-                    Err(e) => {
-                        eprintln!("Error serializing to TOML: {:?}", e);
-                    }
-                },
             }
+            Err(err) => eprintln!("Error: page {}: {}", page.page(), err),
+        }
+    }
+
+    if let Some(total_results) = total_results.filter(|_| error.is_none()) {
+        if collected != total_results as usize {
+            eprintln!(
+                "Warning: collected {} of the {} advertised audiobooks",
+                collected, total_results
+            );
+        }
+    }
+
+    if format != OutputFormat::Jsonl {
+        write_audiobooks(&audiobooks, format, &mut out)?;
+    }
+    out.flush()?;
+
+    match error {
+        Some(err) => {
+            if collected > 0 {
+                eprintln!(
+                    "Wrote the {} audiobooks fetched before the error",
+                    collected
+                );
+            }
+            Err(err.into())
         }
-        Err(err) => eprintln!("Error: {}", err),
+        None => Ok(()),
+    }
+}
+
+/// Returns the exit code of a run failing with `err`: 3 when Audible serves a captcha, 4 when
+/// a page has an unexpected layout, 5 for network and HTTP errors and 1 otherwise; 2 is left
+/// to invalid arguments.
+fn exit_code(err: &(dyn Error + 'static)) -> i32 {
+    match err.downcast_ref::<audible_scraper::Error>() {
+        Some(audible_scraper::Error::Blocked { .. }) => 3,
+        Some(audible_scraper::Error::UnexpectedLayout { .. }) => 4,
+        Some(audible_scraper::Error::Transport(_) | audible_scraper::Error::Status { .. }) => 5,
+        _ => 1,
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse();
+    let rate_limiter = args.rate.map(RateLimiter::new);
+
+    if let Some(path) = args.profile.as_deref() {
+        let profile = load_profile(path).unwrap_or_else(|err| {
+            eprintln!("Error: cannot load profile {}: {}", path.display(), err);
+            std::process::exit(2);
+        });
+
+        for unknown in args
+            .searches
+            .iter()
+            .filter(|name| !profile.searches.iter().any(|search| &search.name == *name))
+        {
+            eprintln!("Warning: no search named {} in {}", unknown, path.display());
+        }
+
+        let searches = profile
+            .searches
+            .into_iter()
+            .filter(|search| args.searches.is_empty() || args.searches.contains(&search.name));
+        // The remaining searches still run after a failure, which sets the exit code
+        let mut failure = None;
+        for search in searches {
+            eprintln!("Running search {}", search.name);
+            if let Err(err) = run(
+                &builder(&args, search.params, rate_limiter.as_ref()),
+                search.format,
+                search.output.as_deref(),
+            )
+            .await
+            {
+                eprintln!("Error: search {}: {}", search.name, err);
+                failure.get_or_insert(exit_code(&*err));
+            }
+        }
+        if let Some(code) = failure {
+            std::process::exit(code);
+        }
+        return;
+    }
+
+    let params = match args.url.as_deref() {
+        Some(url) => QueryParams::try_from(url).unwrap_or_else(|err| {
+            eprintln!("Error: {}", err);
+            std::process::exit(2);
+        }),
+        None => search_params(&args),
+    };
+
+    if let Err(err) = run(
+        &builder(&args, params, rate_limiter.as_ref()),
+        args.format,
+        None,
+    )
+    .await
+    {
+        eprintln!("Error: {}", err);
+        std::process::exit(exit_code(&*err));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The keys of the searches must match the serialized fields of `QueryParams`, so that no
+    /// valid key is reported as unknown.
+    #[test]
+    fn test_profile_search_keys() {
+        let params = QueryParams::new()
+            .keywords("dune")
+            .title("Dune")
+            .author("Frank Herbert")
+            .narrator("Scott Brick")
+            .publisher("Macmillan Audio")
+            .category("18580606011")
+            .released_within(ReleasedWithin::Last30Days)
+            .language(Language::English)
+            .length(Length::OverTwentyHours)
+            .refinement("feature_nine_browse-bin", "18685580011")
+            .sort(SortOrder::TitleAsc);
+        let serde_json::Value::Object(fields) = serde_json::to_value(params).unwrap() else {
+            panic!("QueryParams must serialize to an object");
+        };
+
+        let mut keys: Vec<&str> = ["name", "format", "output"]
+            .into_iter()
+            .chain(fields.keys().map(String::as_str))
+            .collect();
+        let mut expected = PROFILE_SEARCH_KEYS.to_vec();
+        keys.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(keys, expected);
     }
 }
@@ -0,0 +1,296 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+use crate::Error;
+
+/// The order in which a storefront renders the day, month and year of a numeric date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateOrder {
+    /// `30-07-20`, `30.07.2020`, `30/07/2020`
+    DayMonthYear,
+    /// `07-30-20`
+    MonthDayYear,
+    /// `2020/07/30`
+    YearMonthDay,
+}
+
+/// An Audible storefront.
+///
+/// The marketplace drives the host used to build search URLs, the default `Accept-Language`
+/// header sent by the scraper and the locale used to parse the search results.
+///
+/// # Examples
+///
+/// ```
+/// use audible_scraper::Marketplace;
+///
+/// let marketplace: Marketplace = "uk".parse().unwrap();
+///
+/// assert_eq!(marketplace, Marketplace::Uk);
+/// assert_eq!(marketplace.host(), "www.audible.co.uk");
+/// assert_eq!(Marketplace::from_host("www.audible.co.uk"), Some(Marketplace::Uk));
+/// assert_eq!(
+///     marketplace.product_url("B002V1OF70").as_str(),
+///     "https://www.audible.co.uk/pd/B002V1OF70"
+/// );
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[serde(rename_all = "lowercase")]
+pub enum Marketplace {
+    /// audible.com
+    Us,
+    /// audible.co.uk
+    Uk,
+    /// audible.de
+    De,
+    /// audible.fr
+    Fr,
+    /// audible.it
+    It,
+    /// audible.es
+    #[default]
+    Es,
+    /// audible.co.jp
+    Jp,
+    /// audible.com.au
+    Au,
+    /// audible.ca
+    Ca,
+    /// audible.in
+    In,
+}
+
+impl Marketplace {
+    /// Page sizes offered by the page size dropdown, the same on every storefront; any other
+    /// value is silently replaced by Audible.
+    pub const PAGE_SIZES: [u32; 4] = [20, 30, 40, 50];
+
+    pub const ALL: [Marketplace; 10] = [
+        Marketplace::Us,
+        Marketplace::Uk,
+        Marketplace::De,
+        Marketplace::Fr,
+        Marketplace::It,
+        Marketplace::Es,
+        Marketplace::Jp,
+        Marketplace::Au,
+        Marketplace::Ca,
+        Marketplace::In,
+    ];
+
+    /// Short code of the marketplace, e.g. `"es"`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Marketplace::Us => "us",
+            Marketplace::Uk => "uk",
+            Marketplace::De => "de",
+            Marketplace::Fr => "fr",
+            Marketplace::It => "it",
+            Marketplace::Es => "es",
+            Marketplace::Jp => "jp",
+            Marketplace::Au => "au",
+            Marketplace::Ca => "ca",
+            Marketplace::In => "in",
+        }
+    }
+
+    /// Host name of the storefront, e.g. `"www.audible.es"`.
+    pub fn host(&self) -> &'static str {
+        match self {
+            Marketplace::Us => "www.audible.com",
+            Marketplace::Uk => "www.audible.co.uk",
+            Marketplace::De => "www.audible.de",
+            Marketplace::Fr => "www.audible.fr",
+            Marketplace::It => "www.audible.it",
+            Marketplace::Es => "www.audible.es",
+            Marketplace::Jp => "www.audible.co.jp",
+            Marketplace::Au => "www.audible.com.au",
+            Marketplace::Ca => "www.audible.ca",
+            Marketplace::In => "www.audible.in",
+        }
+    }
+
+    /// Root URL of the storefront, e.g. `https://www.audible.es/`.
+    pub fn base_url(&self) -> Url {
+        Url::parse(&format!("https://{}/", self.host())).expect("marketplace hosts are valid")
+    }
+
+    /// Canonical product page of the given ASIN, e.g. `https://www.audible.es/pd/8418107405`.
+    pub fn product_url(&self, asin: &str) -> Url {
+        let mut url = self.base_url();
+        url.path_segments_mut()
+            .expect("marketplace URLs have a path")
+            .pop_if_empty()
+            .extend(["pd", asin]);
+        url
+    }
+
+    /// Default `Accept-Language` header sent to the storefront.
+    pub fn accept_language(&self) -> &'static str {
+        match self {
+            Marketplace::Us => "en-US,en;q=0.9",
+            Marketplace::Uk => "en-GB,en;q=0.9",
+            Marketplace::De => "de-DE,de;q=0.9,en;q=0.8",
+            Marketplace::Fr => "fr-FR,fr;q=0.9,en;q=0.8",
+            Marketplace::It => "it-IT,it;q=0.9,en;q=0.8",
+            Marketplace::Es => "es-ES,es;q=0.9,en;q=0.8",
+            Marketplace::Jp => "ja-JP,ja;q=0.9,en;q=0.8",
+            Marketplace::Au => "en-AU,en;q=0.9",
+            Marketplace::Ca => "en-CA,en;q=0.9,fr-CA;q=0.8",
+            Marketplace::In => "en-IN,en;q=0.9",
+        }
+    }
+
+    /// ISO 4217 code of the currency prices are shown in.
+    pub fn currency(&self) -> &'static str {
+        match self {
+            Marketplace::Us => "USD",
+            Marketplace::Uk => "GBP",
+            Marketplace::De | Marketplace::Fr | Marketplace::It | Marketplace::Es => "EUR",
+            Marketplace::Jp => "JPY",
+            Marketplace::Au => "AUD",
+            Marketplace::Ca => "CAD",
+            Marketplace::In => "INR",
+        }
+    }
+
+    /// Character used to separate the decimals of prices, e.g. `,` in `18,99 €`.
+    pub fn decimal_separator(&self) -> char {
+        match self {
+            Marketplace::De | Marketplace::Fr | Marketplace::It | Marketplace::Es => ',',
+            _ => '.',
+        }
+    }
+
+    /// Order of the numeric release dates rendered in the search results.
+    pub fn date_order(&self) -> DateOrder {
+        match self {
+            Marketplace::Us | Marketplace::Ca => DateOrder::MonthDayYear,
+            Marketplace::Jp => DateOrder::YearMonthDay,
+            _ => DateOrder::DayMonthYear,
+        }
+    }
+
+    /// Month names in the language of the storefront, used to parse long-form dates.
+    pub fn month_names(&self) -> &'static [&'static str; 12] {
+        const EN: [&str; 12] = [
+            "january",
+            "february",
+            "march",
+            "april",
+            "may",
+            "june",
+            "july",
+            "august",
+            "september",
+            "october",
+            "november",
+            "december",
+        ];
+        const DE: [&str; 12] = [
+            "januar",
+            "februar",
+            "märz",
+            "april",
+            "mai",
+            "juni",
+            "juli",
+            "august",
+            "september",
+            "oktober",
+            "november",
+            "dezember",
+        ];
+        const FR: [&str; 12] = [
+            "janvier",
+            "février",
+            "mars",
+            "avril",
+            "mai",
+            "juin",
+            "juillet",
+            "août",
+            "septembre",
+            "octobre",
+            "novembre",
+            "décembre",
+        ];
+        const IT: [&str; 12] = [
+            "gennaio",
+            "febbraio",
+            "marzo",
+            "aprile",
+            "maggio",
+            "giugno",
+            "luglio",
+            "agosto",
+            "settembre",
+            "ottobre",
+            "novembre",
+            "dicembre",
+        ];
+        const ES: [&str; 12] = [
+            "enero",
+            "febrero",
+            "marzo",
+            "abril",
+            "mayo",
+            "junio",
+            "julio",
+            "agosto",
+            "septiembre",
+            "octubre",
+            "noviembre",
+            "diciembre",
+        ];
+
+        match self {
+            Marketplace::De => &DE,
+            Marketplace::Fr => &FR,
+            Marketplace::It => &IT,
+            Marketplace::Es => &ES,
+            _ => &EN,
+        }
+    }
+
+    /// Finds the marketplace served by the given host, with or without the `www.` prefix.
+    pub fn from_host(host: &str) -> Option<Self> {
+        let host = host.strip_prefix("www.").unwrap_or(host);
+        Self::ALL
+            .into_iter()
+            .find(|marketplace| marketplace.host().strip_prefix("www.") == Some(host))
+    }
+
+    /// Finds the marketplace serving the given URL.
+    pub fn from_url(url: &Url) -> Option<Self> {
+        url.host_str().and_then(Self::from_host)
+    }
+}
+
+impl fmt::Display for Marketplace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+impl FromStr for Marketplace {
+    type Err = Error;
+
+    /// Parses the code or the host of a marketplace, e.g. `"uk"` or `"www.audible.co.uk"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Parse`] if `s` is neither the code nor the host of a marketplace.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_ascii_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|marketplace| marketplace.code() == s)
+            .or_else(|| Self::from_host(&s))
+            .ok_or_else(|| Error::parse("marketplace", format!("unknown code or host {s:?}")))
+    }
+}
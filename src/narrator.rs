@@ -0,0 +1,45 @@
+use serde::Serialize;
+use url::Url;
+
+/// A narrator credited on an audio book.
+///
+/// # Fields
+///
+/// * `name` - The name of the narrator, with HTML entities decoded.
+/// * `url` - The Audible search for titles narrated by them (`searchNarrator`).
+///
+/// # Examples
+///
+/// ```
+/// use url::Url;
+/// use audible_scraper::Narrator;
+///
+/// let url = Url::parse("https://www.audible.es/search?searchNarrator=Jordi+Salas").unwrap();
+/// let narrator = Narrator::new(String::from("Jordi Salas"), Some(url));
+///
+/// assert_eq!(narrator.name(), "Jordi Salas");
+/// assert_eq!(
+///     narrator.url().map(Url::as_str),
+///     Some("https://www.audible.es/search?searchNarrator=Jordi+Salas")
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Narrator {
+    name: String,
+    url: Option<Url>,
+}
+
+impl Narrator {
+    pub fn new(name: String, url: Option<Url>) -> Self {
+        Self { name, url }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn url(&self) -> Option<&Url> {
+        self.url.as_ref()
+    }
+}
@@ -0,0 +1,28 @@
+use serde::Serialize;
+use std::fmt;
+
+/// What a page served for a search turned out to be, see [`PageResult::kind`](crate::PageResult::kind).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum PageKind {
+    /// A page listing at least one result.
+    Results,
+    /// A search page reporting that the search has no results.
+    NoResults,
+    /// A captcha or robot check served instead of the requested page.
+    Blocked,
+    /// A page without results nor the markers of an empty search, e.g. after a redesign of the
+    /// search pages or an error page.
+    UnexpectedLayout,
+}
+
+impl fmt::Display for PageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PageKind::Results => "results",
+            PageKind::NoResults => "noResults",
+            PageKind::Blocked => "blocked",
+            PageKind::UnexpectedLayout => "unexpectedLayout",
+        })
+    }
+}
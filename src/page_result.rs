@@ -1,8 +1,14 @@
-use scraper::{Html, Selector};
+use regex::Regex;
+use scraper::{error::SelectorErrorKind, ElementRef, Html, Selector};
 use serde::Serialize;
+use std::sync::LazyLock;
 use url::Url;
 
-use crate::{extract_date, AudioBook};
+use crate::{
+    extract_date, extract_runtime, extract_series_position, AudioBook, Author, CoverImage,
+    Diagnostic, Error, Facet, FacetOption, Marketplace, Narrator, PageKind, Price, Prices,
+    ProductKind, Rating, Result, Series,
+};
 
 /// Represents the result of a page fetch operation, containing information about the current page,
 /// navigation to next and previous pages, and the content of the page.
@@ -14,16 +20,173 @@ use crate::{extract_date, AudioBook};
 /// - `next_page_url`: The URL of the next page, if available.
 /// - `prev_page_url`: The URL of the previous page, if available.
 /// - `url`: The URL of the current page.
-/// - `body`: The HTML content of the current page (not serialized).
+/// - `marketplace`: The storefront that served the page, used to parse locale-specific data.
+/// - `total_results`: The number of results of the whole search, e.g. `113` for "1 - 50 de 113 resultados".
+/// - `total_pages`: The number of pages of the whole search.
+/// - `page_size`: The number of results per page selected in the page size dropdown.
+/// - `kind`: Whether the page lists results, reports an empty search, is a captcha or is not recognized.
+/// - `audiobooks`, `diagnostics`, `facets`: The data extracted from the HTML content of the page (not serialized).
 ///
 /// # Methods
 /// - `new(url: Url, body: String) -> Self`: Constructs a new `PageResult` from the given URL and HTML body.
-/// - `collect(&self) -> Result<Vec<AudioBook>, Box<dyn std::error::Error>>`: Extracts audiobook details from the page content.
+/// - `with_marketplace(url: Url, body: String, marketplace: Marketplace) -> Self`: Same as `new` for an explicit storefront.
+/// - `collect(&self) -> Result<Vec<AudioBook>>`: Extracts audiobook details from the page content.
+/// - `collect_lenient(&self) -> Result<(Vec<AudioBook>, Vec<Diagnostic>)>`: Extracts the items that could be parsed and reports the others.
+/// - `kind(&self) -> PageKind`: Returns what the page turned out to be.
 /// - `has_next(&self) -> bool`: Returns `true` if there is a next page.
 /// - `has_prev(&self) -> bool`: Returns `true` if there is a previous page.
 /// - `next_page_url(&self) -> Option<Url>`: Returns the URL of the next page, if available.
 /// - `prev_page_url(&self) -> Option<Url>`: Returns the URL of the previous page, if available.
+/// - `facets(&self) -> Vec<Facet>`: Returns the refinements offered for the current search, such as the categories or lengths.
+/// - `page_urls(&self) -> Option<Vec<Url>>`: Returns the URLs of every page of the search, if the number of pages is known.
 use html_escape::decode_html_entities;
+
+// `1 - 50 de 113 resultados`, `1-20 of 1,234 results`, `1 - 20 sur 1 234 résultats`
+static RESULT_NUMBER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\d+(?:[.,\u{a0}\u{202f} ]\d{3})*").unwrap());
+static RESULT_RANGE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(\d+(?:[.,\u{a0}\u{202f} ]\d{3})*)\s*[-–]\s*(\d+(?:[.,\u{a0}\u{202f} ]\d{3})*)")
+        .unwrap()
+});
+
+/// Texts only found in the captcha pages Audible serves to suspected robots, whose title is
+/// [`ROBOT_CHECK_TITLE`].
+const CAPTCHA_MARKERS: [&str; 2] = ["/errors/validateCaptcha", "captchacharacters"];
+const ROBOT_CHECK_TITLE: &str = "Robot Check";
+
+// `No results for "xyzzy"`, `No hay resultados para "xyzzy"`, `Keine Ergebnisse für "xyzzy"`,
+// `「xyzzy」の検索結果はありません`; Japanese has no word boundaries
+static NO_RESULTS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(no results|no hay resultados|keine ergebnisse|aucun résultat|nessun risultato)|結果はありません|見つかりませんでした",
+    )
+    .unwrap()
+});
+
+/// Query parameters Audible appends to its links to track clicks.
+pub(crate) const TRACKING_PARAMS: [&str; 8] = [
+    "ref",
+    "ref_pageloadid",
+    "pf_rd_p",
+    "pf_rd_r",
+    "pageLoadId",
+    "creativeId",
+    "qid",
+    "sr",
+];
+
+/// Maximum number of characters of raw HTML kept in a [`Diagnostic`].
+const SNIPPET_LEN: usize = 200;
+
+/// Selectors for the data of the page as a whole: its pagination, summary and refinements.
+struct PageSelectors {
+    item: Selector,
+    title: Selector,
+    current_page: Selector,
+    next_button: Selector,
+    prev_button: Selector,
+    summary: Selector,
+    heading: Selector,
+    page_number: Selector,
+    page_size: Selector,
+    categories_heading: Selector,
+    category: Selector,
+    filter: Selector,
+    filter_name: Selector,
+    filter_option: Selector,
+    input: Selector,
+    dropdown: Selector,
+    dropdown_option: Selector,
+}
+
+impl PageSelectors {
+    fn new() -> Result<Self, SelectorErrorKind<'static>> {
+        Ok(Self {
+            item: Selector::parse("li.productListItem")?,
+            title: Selector::parse("title")?,
+            current_page: Selector::parse("span.pageNumberElement")?,
+            next_button: Selector::parse(".nextButton a")?,
+            prev_button: Selector::parse(".previousButton a")?,
+            summary: Selector::parse(".resultsSummarySubheading")?,
+            heading: Selector::parse("h1, h2, h3, .resultsSummarySubheading")?,
+            page_number: Selector::parse(".pageNumberElement")?,
+            page_size: Selector::parse("select.refinementDropdown-pageSize option[selected]")?,
+            categories_heading: Selector::parse("#a-categories")?,
+            category: Selector::parse(".categories a.refinementFormLink[href]")?,
+            filter: Selector::parse(".otherFilters .bc-accordion-row")?,
+            filter_name: Selector::parse(".bc-accordion-header-text")?,
+            filter_option: Selector::parse("a.refinementFormRadio")?,
+            input: Selector::parse("input[name][value]")?,
+            dropdown: Selector::parse(
+                "select.refinementDropdown-sort, select.refinementDropdown-pageSize",
+            )?,
+            dropdown_option: Selector::parse("option")?,
+        })
+    }
+}
+
+static PAGE_SELECTORS: LazyLock<PageSelectors> = LazyLock::new(|| PageSelectors::new().unwrap());
+
+/// Selectors for the data of each item of the page.
+struct ItemSelectors {
+    button: Selector,
+    asin: Selector,
+    product_link: Selector,
+    details: Selector,
+    subtitle: Selector,
+    cover: Selector,
+    author: Selector,
+    narrator: Selector,
+    language: Selector,
+    series: Selector,
+    runtime: Selector,
+    release_date: Selector,
+    rating_stars: Selector,
+    rating_count: Selector,
+    regular_price: Selector,
+    sale_price: Selector,
+    member_price: Selector,
+    price_text: Selector,
+    credit_cost: Selector,
+}
+
+impl ItemSelectors {
+    fn new() -> Result<Self, SelectorErrorKind<'static>> {
+        Ok(Self {
+            button: Selector::parse("button[data-mp3]")?,
+            asin: Selector::parse("[data-asin]")?,
+            product_link: Selector::parse("h3 a[href]")?,
+            details: Selector::parse(".bc-popover-inner li")?,
+            subtitle: Selector::parse("li.subtitle span.bc-text")?,
+            cover: Selector::parse("picture source[srcset], picture img")?,
+            author: Selector::parse("li.authorLabel span.bc-text a")?,
+            narrator: Selector::parse("li.narratorLabel span.bc-text a")?,
+            language: Selector::parse("li.languageLabel span.bc-text")?,
+            series: Selector::parse("li.seriesLabel span.bc-text")?,
+            runtime: Selector::parse("li.runtimeLabel span.bc-text")?,
+            release_date: Selector::parse("li.releaseDateLabel span.bc-text")?,
+            rating_stars: Selector::parse("li.ratingsLabel span.bc-pub-offscreen")?,
+            rating_count: Selector::parse("li.ratingsLabel span.bc-text:not(.bc-pub-offscreen)")?,
+            regular_price: Selector::parse("p.buybox-regular-price:not(.bc-hidden)")?,
+            sale_price: Selector::parse("p.buybox-sale-price:not(.bc-hidden)")?,
+            member_price: Selector::parse("p.buybox-member-price:not(.bc-hidden)")?,
+            price_text: Selector::parse("span.bc-text:not(.adblBuyBoxCreditCost)")?,
+            credit_cost: Selector::parse(".adblBuyBoxCreditCost:not(.bc-hidden)")?,
+        })
+    }
+}
+
+static ITEM_SELECTORS: LazyLock<ItemSelectors> = LazyLock::new(|| ItemSelectors::new().unwrap());
+
+/// Returns the HTML of `el` with its whitespace collapsed, truncated to [`SNIPPET_LEN`] characters.
+fn snippet(el: ElementRef) -> String {
+    let html = el.html().split_whitespace().collect::<Vec<_>>().join(" ");
+    match html.char_indices().nth(SNIPPET_LEN) {
+        Some((end, _)) => format!("{}…", &html[..end]),
+        None => html,
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct PageResult {
@@ -33,131 +196,541 @@ pub struct PageResult {
     next_page_url: Option<Url>,
     prev_page_url: Option<Url>,
     url: Url,
+    marketplace: Marketplace,
+    total_results: Option<u32>,
+    total_pages: Option<u32>,
+    page_size: Option<u32>,
+    kind: PageKind,
     #[serde(skip)]
-    body: String,
+    audiobooks: Vec<AudioBook>,
+    #[serde(skip)]
+    diagnostics: Vec<Diagnostic>,
+    #[serde(skip)]
+    facets: Vec<Facet>,
 }
 
 impl PageResult {
     /// Constructs a new `PageResult` from the given URL and HTML body.
+    ///
+    /// The marketplace is inferred from the host of the URL, falling back to the default one.
     pub fn new(url: Url, body: String) -> Self {
-        let document = Html::parse_document(&body);
+        let marketplace = Marketplace::from_url(&url).unwrap_or_default();
+        Self::with_marketplace(url, body, marketplace)
+    }
 
-        // Define selectors for current page, next, and previous buttons
-        let current_page_selector = Selector::parse("span.pageNumberElement").unwrap();
-        let next_button_selector = Selector::parse(".nextButton a").unwrap();
-        let prev_button_selector = Selector::parse(".previousButton a").unwrap();
+    /// Constructs a new `PageResult` from the given URL and HTML body served by `marketplace`.
+    ///
+    /// The body is parsed once, extracting its items and facets along with the pagination.
+    pub fn with_marketplace(url: Url, body: String, marketplace: Marketplace) -> Self {
+        let document = Html::parse_document(&body);
+        let s = &*PAGE_SELECTORS;
 
         // Extract current page number from the <span class="pageNumberElement">
         let page = document
-            .select(&current_page_selector)
+            .select(&s.current_page)
             .filter_map(|el| el.text().next())
             .filter_map(|text| text.parse::<u32>().ok())
             .next()
             .unwrap_or(1);
 
         // Determine next page URL based on the presence and status of the "Next" button
-        let next_page_url = document
-            .select(&next_button_selector)
-            .next()
-            .and_then(|el| {
-                // Check if the button is enabled (aria-disabled should be absent)
-                if el.value().attr("aria-disabled").is_none() {
-                    el.value().attr("href").and_then(|href| url.join(href).ok())
-                } else {
-                    None
-                }
-            });
+        let next_page_url = document.select(&s.next_button).next().and_then(|el| {
+            // Check if the button is enabled (aria-disabled should be absent)
+            if el.value().attr("aria-disabled").is_none() {
+                el.value().attr("href").and_then(|href| url.join(href).ok())
+            } else {
+                None
+            }
+        });
         let has_next = next_page_url.is_some();
 
         // Determine previous page URL based on the presence and status of the "Previous" button
-        let prev_page_url = document
-            .select(&prev_button_selector)
+        let prev_page_url = document.select(&s.prev_button).next().and_then(|el| {
+            // Check if the button is enabled (aria-disabled should be absent)
+            if el.value().attr("aria-disabled").is_none() {
+                el.value().attr("href").and_then(|href| url.join(href).ok())
+            } else {
+                None
+            }
+        });
+        let has_prev = prev_page_url.is_some();
+
+        // Extract the range of results shown and the total from "1 - 50 de 113 resultados"
+        let summary = document
+            .select(&s.summary)
             .next()
-            .and_then(|el| {
-                // Check if the button is enabled (aria-disabled should be absent)
-                if el.value().attr("aria-disabled").is_none() {
-                    el.value().attr("href").and_then(|href| url.join(href).ok())
+            .and_then(|el| parse_results_summary(&el.text().collect::<String>()));
+        let total_results = summary.map(|(_, _, total)| total);
+
+        // Derive the number of pages from the page size, falling back to the pagination links
+        // when the summary is inconsistent, e.g. `0 - 20` on the second page
+        let total_pages = summary
+            .and_then(|(first, last, total)| {
+                let page_size = if page > 1 {
+                    first.checked_sub(1)? / (page - 1)
                 } else {
-                    None
-                }
+                    last.checked_add(1)?.checked_sub(first)?
+                };
+                (page_size > 0).then(|| total.div_ceil(page_size))
+            })
+            .or_else(|| {
+                document
+                    .select(&s.page_number)
+                    .filter_map(|el| el.text().collect::<String>().trim().parse::<u32>().ok())
+                    .max()
             });
-        let has_prev = prev_page_url.is_some();
 
-        Self {
+        // The selected page size, the empty value being Audible's default of 20
+        let page_size = document.select(&s.page_size).next().and_then(|el| {
+            match el.value().attr("value").unwrap_or_default() {
+                "" => Some(20),
+                value => value.parse().ok(),
+            }
+        });
+
+        let kind = classify(&document, &body);
+
+        let mut result = Self {
             page,
             has_next,
             has_prev,
             next_page_url,
             prev_page_url,
             url,
-            body,
+            marketplace,
+            total_results,
+            total_pages,
+            page_size,
+            kind,
+            audiobooks: Vec::new(),
+            diagnostics: Vec::new(),
+            facets: Vec::new(),
+        };
+        if kind == PageKind::Results {
+            (result.audiobooks, result.diagnostics) = result.collect_items(&document);
         }
+        result.facets = result.collect_facets(&document);
+        result
     }
 
     /// Extracts audiobook details from the page content.
     /// Returns a vector of `AudioBook` instances.
     /// # Errors
-    /// Returns an error if any required data is missing or cannot be parsed.
-    pub fn collect(&self) -> Result<Vec<AudioBook>, Box<dyn std::error::Error>> {
-        let document = Html::parse_document(&self.body);
-
-        // Define selectors for each piece of data
-        let item_selector = Selector::parse("li.productListItem")?;
-        let button_selector = Selector::parse("button[data-mp3]")?;
-        let narrator_selector = Selector::parse("li.narratorLabel span.bc-text a")?;
-        let language_selector = Selector::parse("li.languageLabel span.bc-text")?;
-        let release_date_selector = Selector::parse("li.releaseDateLabel span.bc-text")?;
-        let items = document.select(&item_selector);
-        // Collect audiobook details
+    /// Returns [`Error::Blocked`] or [`Error::UnexpectedLayout`] if the page is a captcha or is
+    /// not recognized, see [`PageResult::kind`], and an error if any required data is missing
+    /// or cannot be parsed. A page reporting an empty search has no audiobooks. See
+    /// [`PageResult::collect_lenient`] to keep the items that could be parsed.
+    pub fn collect(&self) -> Result<Vec<AudioBook>> {
+        let (audiobooks, diagnostics) = self.collect_lenient()?;
+
+        if let Some(diagnostic) = diagnostics.into_iter().next() {
+            return Err(diagnostic.into());
+        }
+
+        Ok(audiobooks)
+    }
+
+    /// Extracts every audiobook that could be parsed from the page content, along with a
+    /// [`Diagnostic`] for each problem found in an item.
+    ///
+    /// Items without an ASIN, title or language are skipped. Items with a missing narrator,
+    /// an unparsable release date or an invalid sample URL are kept without that field, and
+    /// items without a sample button are returned without a sample URL. A page reporting an
+    /// empty search has no audiobooks.
+    /// # Errors
+    /// Returns [`Error::Blocked`] or [`Error::UnexpectedLayout`] if the page is a captcha or is
+    /// not recognized, see [`PageResult::kind`].
+    pub fn collect_lenient(&self) -> Result<(Vec<AudioBook>, Vec<Diagnostic>)> {
+        let url = self.url.clone();
+        match self.kind {
+            PageKind::Results | PageKind::NoResults => {}
+            PageKind::Blocked => return Err(Error::Blocked { url }),
+            PageKind::UnexpectedLayout => return Err(Error::UnexpectedLayout { url }),
+        }
+
+        Ok((self.audiobooks.clone(), self.diagnostics.clone()))
+    }
+
+    /// Extracts the items of `document`, the parsed body of the page.
+    fn collect_items(&self, document: &Html) -> (Vec<AudioBook>, Vec<Diagnostic>) {
         let mut audiobooks = Vec::new();
+        let mut diagnostics = Vec::new();
 
-        if items.clone().count() == 0 {
-            return Err("No items found".into());
+        for (index, item) in document.select(&PAGE_SELECTORS.item).enumerate() {
+            if let Some(audiobook) = self.collect_item(index, item, &mut diagnostics) {
+                audiobooks.push(audiobook);
+            }
         }
 
-        for item in items {
-            if let Some(button) = item.select(&button_selector).next() {
-                let mp3_url = Url::parse(
-                    button
-                        .value()
-                        .attr("data-mp3")
-                        .ok_or("Unexpected missing data-mp3")?,
-                )?;
-
-                let title = item
-                    .attr("aria-label")
-                    .map(|label| decode_html_entities(label).to_string())
-                    .ok_or("Title not found")?;
-
-                let narrator = item
-                    .select(&narrator_selector)
-                    .next()
-                    .map(|el| el.inner_html())
-                    .ok_or("Narrator not found")?;
+        (audiobooks, diagnostics)
+    }
 
-                let language = item
-                    .select(&language_selector)
-                    .next()
-                    .map(|el| el.inner_html().trim().to_string())
-                    .ok_or("Language not found")?;
+    /// Extracts a single item of the page, recording any problem found in `diagnostics`.
+    ///
+    /// Returns `None` if the item lacks a field required to build an `AudioBook`.
+    fn collect_item(
+        &self,
+        index: usize,
+        item: ElementRef,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) -> Option<AudioBook> {
+        let s = &*ITEM_SELECTORS;
+        let button = item.select(&s.button).next();
+
+        let asin = item
+            .select(&s.asin)
+            .next()
+            .and_then(|el| el.value().attr("data-asin"))
+            .or_else(|| button.and_then(|el| el.value().attr("sample-asin")))
+            .filter(|asin| !asin.is_empty())
+            .map(str::to_string);
+
+        let mut report = |field, message: &str, el: ElementRef, skipped| {
+            diagnostics.push(Diagnostic::new(
+                index,
+                asin.clone(),
+                field,
+                message,
+                snippet(el),
+                skipped,
+            ));
+        };
+
+        let Some(asin) = asin.clone() else {
+            report("asin", "ASIN not found", item, true);
+            return None;
+        };
+
+        let Some(title) = item
+            .attr("aria-label")
+            .map(|label| decode_html_entities(label).to_string())
+        else {
+            report("title", "Title not found", item, true);
+            return None;
+        };
+
+        let Some(language) = item
+            .select(&s.language)
+            .next()
+            .map(|el| el.inner_html().trim().to_string())
+        else {
+            report("language", "Language not found", item, true);
+            return None;
+        };
+
+        let sample_url = button.and_then(|el| {
+            let mp3 = el.value().attr("data-mp3").unwrap_or_default();
+            Url::parse(mp3)
+                .inspect_err(|err| {
+                    report(
+                        "sampleUrl",
+                        &format!("Invalid sample URL: {err}"),
+                        el,
+                        false,
+                    )
+                })
+                .ok()
+        });
+
+        let subtitle = item
+            .select(&s.subtitle)
+            .next()
+            .map(|el| decode_html_entities(el.text().collect::<String>().trim()).to_string())
+            .filter(|subtitle| !subtitle.is_empty());
+
+        let kind = {
+            let path = item
+                .select(&s.product_link)
+                .next()
+                .and_then(|el| el.value().attr("href"))
+                .and_then(|href| self.url.join(href).ok())
+                .map(|url| url.path().to_string())
+                .unwrap_or_default();
+            // The format and program lines, e.g. `Versión íntegra` or `Podcast`: the popover
+            // lines after the title without a label such as `De:` or `Narrado por:`
+            let format_lines: Vec<String> = item
+                .select(&s.details)
+                .skip(1)
+                .map(|el| el.text().collect::<String>().trim().to_lowercase())
+                .filter(|line| !line.contains(':'))
+                .collect();
+            let has_format = |markers: &[&str]| {
+                format_lines
+                    .iter()
+                    .any(|line| markers.iter().any(|marker| line.contains(marker)))
+            };
+
+            if path.starts_with("/podcast/") || has_format(&["podcast"]) {
+                ProductKind::Podcast
+            } else if has_format(&["audible original", "original de audible"]) {
+                ProductKind::AudibleOriginal
+            } else {
+                ProductKind::Audiobook
+            }
+        };
+
+        let mut covers: Vec<CoverImage> = Vec::new();
+        for el in item.select(&s.cover) {
+            let srcset = el.value().attr("srcset").unwrap_or_default();
+            let candidates = srcset
+                .split(',')
+                .filter_map(|candidate| candidate.split_whitespace().next())
+                .chain(el.value().attr("src"));
+
+            for cover in candidates.filter_map(|src| self.url.join(src).ok()) {
+                if !covers.iter().any(|known| known.url() == &cover) {
+                    covers.push(CoverImage::new(cover));
+                }
+            }
+        }
+        covers.sort_by_key(|cover| cover.size());
+
+        let authors = item
+            .select(&s.author)
+            .map(|el| {
+                let name = decode_html_entities(el.text().collect::<String>().trim()).to_string();
+                let url = el
+                    .value()
+                    .attr("href")
+                    .and_then(|href| self.resolve_link(href));
+                Author::new(name, url)
+            })
+            .collect();
+
+        let narrators: Vec<Narrator> = item
+            .select(&s.narrator)
+            .map(|el| {
+                let name = decode_html_entities(el.text().collect::<String>().trim()).to_string();
+                let url = el
+                    .value()
+                    .attr("href")
+                    .and_then(|href| self.resolve_link(href));
+                Narrator::new(name, url)
+            })
+            .collect();
+
+        if narrators.is_empty() {
+            report("narrators", "Narrator not found", item, false);
+        }
+
+        let release_date = item.select(&s.release_date).next().and_then(|el| {
+            extract_date(el.inner_html().as_str(), self.marketplace)
+                .inspect_err(|err| report("releaseDate", &err.to_string(), el, false))
+                .ok()
+        });
+
+        let series = item
+            .select(&s.series)
+            .flat_map(|el| self.collect_series(el))
+            .collect();
+
+        let runtime = item
+            .select(&s.runtime)
+            .next()
+            .and_then(|el| extract_runtime(&el.text().collect::<String>()));
+
+        let rating = item
+            .select(&s.rating_stars)
+            .next()
+            .zip(item.select(&s.rating_count).last())
+            .and_then(|(stars, count)| {
+                Rating::parse(
+                    &stars.text().collect::<String>(),
+                    &count.text().collect::<String>(),
+                )
+            });
+
+        let price = |selector: &Selector| {
+            item.select(selector).next().and_then(|el| {
+                let text = el
+                    .select(&s.price_text)
+                    .flat_map(|el| el.text())
+                    .collect::<String>();
+                Price::parse(&text, self.marketplace)
+            })
+        };
+        let credits = item.select(&s.credit_cost).next().and_then(|el| {
+            el.text()
+                .collect::<String>()
+                .split_whitespace()
+                .find_map(|word| word.parse().ok())
+        });
+        let prices = Prices::new(
+            price(&s.regular_price),
+            price(&s.sale_price),
+            price(&s.member_price),
+            credits,
+        );
+
+        Some(
+            AudioBook::new(
+                asin.clone(),
+                title,
+                narrators,
+                language,
+                release_date,
+                sample_url,
+            )
+            .with_url(self.marketplace.product_url(&asin))
+            .with_kind(kind)
+            .with_subtitle(subtitle)
+            .with_covers(covers)
+            .with_authors(authors)
+            .with_series(series)
+            .with_runtime(runtime)
+            .with_rating(rating)
+            .with_prices(prices),
+        )
+    }
 
-                let release_date = item
-                    .select(&release_date_selector)
+    /// Extracts the series of a `seriesLabel`, e.g. `Serie: <a>Trilogía de Estocolmo</a>, libro 1`.
+    ///
+    /// The position of each series is taken from the text that follows its link.
+    fn collect_series(&self, label: ElementRef) -> Vec<Series> {
+        let mut entries: Vec<(String, Option<Url>, String)> = Vec::new();
+
+        for child in label.children() {
+            if let Some(link) = ElementRef::wrap(child).filter(|el| el.value().name() == "a") {
+                let name = decode_html_entities(link.text().collect::<String>().trim()).to_string();
+                let url = link
+                    .value()
+                    .attr("href")
+                    .and_then(|href| self.resolve_link(href));
+                entries.push((name, url, String::new()));
+            } else if let (Some(text), Some(entry)) = (child.value().as_text(), entries.last_mut())
+            {
+                entry.2.push_str(text);
+            }
+        }
+
+        entries
+            .into_iter()
+            .map(|(name, url, text)| Series::new(name, url, extract_series_position(&text)))
+            .collect()
+    }
+
+    /// Extracts the refinements the page offers for the current search: the categories, the
+    /// filters of the sidebar (release date, length, language, ...), the sort order and the
+    /// page size.
+    pub fn facets(&self) -> Vec<Facet> {
+        self.facets.clone()
+    }
+
+    /// Extracts the facets of `document`, the parsed body of the page.
+    fn collect_facets(&self, document: &Html) -> Vec<Facet> {
+        let s = &*PAGE_SELECTORS;
+        let text =
+            |el: ElementRef| decode_html_entities(el.text().collect::<String>().trim()).to_string();
+        let mut facets = Vec::new();
+
+        // Categories are plain links to the same search with a `node` parameter
+        let categories: Vec<FacetOption> = document
+            .select(&s.category)
+            .filter_map(|el| {
+                let url = self.resolve_link(el.value().attr("href")?)?;
+                let node = url
+                    .query_pairs()
+                    .find(|(key, _)| key == "node")
+                    .map(|(_, value)| value.into_owned())?;
+                Some(FacetOption::new(
+                    "node".to_string(),
+                    node,
+                    &text(el),
+                    false,
+                    Some(url),
+                ))
+            })
+            .collect();
+        if !categories.is_empty() {
+            let name = document
+                .select(&s.categories_heading)
+                .next()
+                .map(text)
+                .unwrap_or_default();
+            facets.push(Facet::new("node".to_string(), name, categories));
+        }
+
+        // Each filter of the sidebar is an accordion row of checkboxes named after its parameter
+        for row in document.select(&s.filter) {
+            let options: Vec<FacetOption> = row
+                .select(&s.filter_option)
+                .filter_map(|el| {
+                    let input = el.select(&s.input).next()?.value();
+                    let label = input
+                        .attr("aria-label")
+                        .map(|label| decode_html_entities(label).to_string())
+                        .unwrap_or_else(|| text(el));
+                    Some(FacetOption::new(
+                        input.attr("name")?.to_string(),
+                        input.attr("value")?.to_string(),
+                        &label,
+                        input.attr("checked").is_some(),
+                        el.value()
+                            .attr("href")
+                            .and_then(|href| self.resolve_link(href)),
+                    ))
+                })
+                .collect();
+
+            if let Some(key) = options.first().map(|option| option.key().to_string()) {
+                let name = row
+                    .select(&s.filter_name)
                     .next()
-                    .map(|el| extract_date(el.inner_html().as_str()))
-                    .transpose()?;
-
-                audiobooks.push(AudioBook::new(
-                    title,
-                    narrator,
-                    language,
-                    release_date,
-                    mp3_url,
-                ));
+                    .map(text)
+                    .unwrap_or_default();
+                facets.push(Facet::new(key, name, options));
             }
         }
 
-        Ok(audiobooks)
+        // The sort order and page size are dropdowns labelled by another element
+        for select in document.select(&s.dropdown) {
+            let Some(key) = select.value().attr("name") else {
+                continue;
+            };
+
+            let options = select
+                .select(&s.dropdown_option)
+                .map(|el| {
+                    FacetOption::new(
+                        key.to_string(),
+                        el.value().attr("value").unwrap_or_default().to_string(),
+                        &text(el),
+                        el.value().attr("selected").is_some(),
+                        el.value()
+                            .attr("data-url")
+                            .and_then(|href| self.resolve_link(href)),
+                    )
+                })
+                .collect();
+
+            let name = select
+                .value()
+                .attr("aria-labelledby")
+                .and_then(|id| Selector::parse(&format!("#{id}")).ok())
+                .and_then(|selector| document.select(&selector).next().map(text))
+                .unwrap_or_default();
+            facets.push(Facet::new(key.to_string(), name, options));
+        }
+
+        facets
+    }
+
+    /// Resolves a link found in the page, dropping the tracking parameters Audible adds to it.
+    fn resolve_link(&self, href: &str) -> Option<Url> {
+        let mut url = self.url.join(href).ok()?;
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .filter(|(key, _)| !TRACKING_PARAMS.contains(&key.as_ref()))
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+
+        if pairs.is_empty() {
+            url.set_query(None);
+        } else {
+            url.query_pairs_mut().clear().extend_pairs(pairs);
+        }
+
+        Some(url)
     }
 
     pub fn has_next(&self) -> bool {
@@ -183,15 +756,188 @@ impl PageResult {
     pub fn page(&self) -> u32 {
         self.page
     }
+
+    pub fn marketplace(&self) -> Marketplace {
+        self.marketplace
+    }
+
+    /// Returns whether the page lists results, reports an empty search, is a captcha or robot
+    /// check, or is not recognized as a search page.
+    pub fn kind(&self) -> PageKind {
+        self.kind
+    }
+
+    pub fn total_results(&self) -> Option<u32> {
+        self.total_results
+    }
+
+    pub fn total_pages(&self) -> Option<u32> {
+        self.total_pages
+    }
+
+    pub fn page_size(&self) -> Option<u32> {
+        self.page_size
+    }
+
+    /// Returns the URLs of every page of the search, from the first to the last one, by
+    /// setting the `page` parameter of the URL of this page.
+    ///
+    /// Returns `None` if the number of pages is unknown.
+    pub fn page_urls(&self) -> Option<Vec<Url>> {
+        let pairs: Vec<(String, String)> = self
+            .url
+            .query_pairs()
+            .filter(|(key, _)| key != "page")
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+
+        let urls = (1..=self.total_pages?)
+            .map(|page| {
+                let mut url = self.url.clone();
+                url.query_pairs_mut()
+                    .clear()
+                    .extend_pairs(&pairs)
+                    .append_pair("page", &page.to_string());
+                url
+            })
+            .collect();
+
+        Some(urls)
+    }
+}
+
+/// Tells what a page is from its markup: a captcha, a page with result items, a search page
+/// reporting no results, or anything else.
+fn classify(document: &Html, body: &str) -> PageKind {
+    let s = &*PAGE_SELECTORS;
+
+    let robot_check = document
+        .select(&s.title)
+        .any(|el| el.text().collect::<String>().contains(ROBOT_CHECK_TITLE));
+    if robot_check || CAPTCHA_MARKERS.iter().any(|marker| body.contains(marker)) {
+        return PageKind::Blocked;
+    }
+
+    if document.select(&s.item).next().is_some() {
+        return PageKind::Results;
+    }
+
+    // "0 resultados", or a heading such as "No hay resultados para ..."
+    let zero_results = document.select(&s.summary).any(|el| {
+        let text: String = el.text().collect();
+        RESULT_NUMBER
+            .find(&text)
+            .is_some_and(|number| number.as_str() == "0")
+    });
+    let no_results_heading = document
+        .select(&s.heading)
+        .any(|el| NO_RESULTS.is_match(&el.text().collect::<String>()));
+
+    if zero_results || no_results_heading {
+        PageKind::NoResults
+    } else {
+        PageKind::UnexpectedLayout
+    }
+}
+
+/// Parses a results summary, e.g. `1 - 50 de 113 resultados`, into the first and last result
+/// shown and the total number of results.
+fn parse_results_summary(text: &str) -> Option<(u32, u32, u32)> {
+    let number = |text: &str| -> Option<u32> {
+        text.chars()
+            .filter(char::is_ascii_digit)
+            .collect::<String>()
+            .parse()
+            .ok()
+    };
+
+    let range = RESULT_RANGE.captures(text)?;
+    let first = number(&range[1])?;
+    let last = number(&range[2])?;
+    let rest = text.replacen(&range[0], " ", 1);
+    let total = number(RESULT_NUMBER.find(&rest)?.as_str())?;
+
+    Some((first, last, total))
 }
 
 /// Extracts audiobook details from the page content.
 /// Returns a vector of `AudioBook` instances.
 /// # Errors
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
+    use crate::QueryParams;
+    use chrono::NaiveDate;
+    use std::collections::HashSet;
     use std::fs;
+    use std::time::Duration;
+
+    pub(crate) const CAPTCHA_PAGE: &str = r#"<html>
+      <head><title>Audible.es</title></head>
+      <body>
+        <form method="get" action="/errors/validateCaptcha">
+          <input type="text" id="captchacharacters" name="field-keywords">
+        </form>
+      </body>
+    </html>"#;
+
+    pub(crate) const NO_RESULTS_PAGE: &str = r#"<html>
+      <head><title>Audible.es</title></head>
+      <body>
+        <div class="adbl-page">
+          <h1 class="bc-heading">No hay resultados para "xyzzy"</h1>
+        </div>
+      </body>
+    </html>"#;
+
+    /// Search URL of the result page fixtures.
+    const FIXTURE_URL: &str =
+        "https://www.audible.es/search?searchNarrator=Jordi+Salas&sort=title-asc-rank&pageSize=50&page=1";
+
+    static FIRST_PAGE: LazyLock<PageResult> = LazyLock::new(|| fixture("result_first_page.html"));
+    static LAST_PAGE: LazyLock<PageResult> = LazyLock::new(|| fixture("result_last_page.html"));
+
+    fn fixture(name: &str) -> PageResult {
+        let body = fs::read_to_string(format!("test_data/{name}")).unwrap();
+        PageResult::new(Url::parse(FIXTURE_URL).unwrap(), body)
+    }
+
+    /// The first result page fixture, parsed once for all the tests.
+    pub(crate) fn first_page() -> &'static PageResult {
+        &FIRST_PAGE
+    }
+
+    /// The last result page fixture, parsed once for all the tests.
+    pub(crate) fn last_page() -> &'static PageResult {
+        &LAST_PAGE
+    }
+
+    pub(crate) const SAMPLE_BUTTON: &str = r#"<button data-mp3="https://samples.audible.com/bk/adbl/000001/bk_adbl_000001_sample.mp3"></button>"#;
+
+    /// Returns a result item for `asin` and `title`, whose details are the `content` markup.
+    pub(crate) fn item(asin: &str, title: &str, content: &str) -> String {
+        format!(
+            r#"<li class="bc-list-item productListItem" aria-label="{title}">
+              <div data-asin="{asin}"></div>
+              {content}
+            </li>"#
+        )
+    }
+
+    /// Returns a detail line of an item, e.g. `label("languageLabel", "English")`.
+    pub(crate) fn label(class: &str, html: &str) -> String {
+        format!(r#"<li class="bc-list-item {class}"><span class="bc-text">{html}</span></li>"#)
+    }
+
+    /// Returns the details of an item narrated by Scott Brick in English, followed by `labels`.
+    fn details(labels: &str) -> String {
+        let narrator = label(
+            "narratorLabel",
+            r#"<a href="/search?searchNarrator=Scott+Brick">Scott Brick</a>"#,
+        );
+        let language = label("languageLabel", " English ");
+        format!("{SAMPLE_BUTTON}<ul>{narrator}{language}{labels}</ul>")
+    }
 
     #[test]
     fn test_page_result_new() {
@@ -220,11 +966,595 @@ mod tests {
         assert_eq!(first_audiobook.language(), "Español (Castellano)");
         assert!(first_audiobook.release_date().is_some());
         assert_eq!(
-            first_audiobook.sample_url().as_str(),
-            "https://samples.audible.com/bk/rhsp/002067/bk_rhsp_002067_sample.mp3"
+            first_audiobook.sample_url().map(Url::as_str),
+            Some("https://samples.audible.com/bk/rhsp/002067/bk_rhsp_002067_sample.mp3")
+        );
+    }
+
+    #[test]
+    fn test_page_result_collect_authors() {
+        let audiobooks = first_page().collect().unwrap();
+        assert!(audiobooks.iter().all(|book| !book.authors().is_empty()));
+
+        let authors = audiobooks[0].authors();
+        assert_eq!(authors.len(), 1);
+        assert_eq!(authors[0].name(), "Niklas Natt Och Dag");
+        assert_eq!(authors[0].id(), Some("B07NQHVVFZ"));
+        assert_eq!(
+            authors[0].url().map(Url::as_str),
+            Some("https://www.audible.es/author/Niklas-Natt-och-Dag/B07NQHVVFZ")
+        );
+
+        // Authors without an author page link to the author search instead
+        let authors = audiobooks[2].authors();
+        assert_eq!(authors.len(), 2);
+        assert_eq!(authors[0].name(), "Niklas Natt och Dag");
+        assert_eq!(authors[1].name(), "Pontus Sánchez Giménez - translator");
+        assert_eq!(authors[1].id(), None);
+        assert_eq!(
+            authors[1].url().map(Url::as_str),
+            Some("https://www.audible.es/search?searchAuthor=Pontus+S%C3%A1nchez+Gim%C3%A9nez+-+translator")
         );
     }
 
+    #[test]
+    fn test_page_result_collect_narrators() {
+        let audiobooks = first_page().collect().unwrap();
+
+        let narrators = audiobooks[0].narrators();
+        assert_eq!(narrators.len(), 1);
+        assert_eq!(narrators[0].name(), "Jordi Salas");
+        assert_eq!(
+            narrators[0].url().map(Url::as_str),
+            Some("https://www.audible.es/search?searchNarrator=Jordi+Salas")
+        );
+
+        let capuleto = &audiobooks[21];
+        assert_eq!(capuleto.title(), "Capuleto");
+        assert_eq!(capuleto.narrator(), "Roger Serradell");
+        let names: Vec<&str> = capuleto.narrators().iter().map(Narrator::name).collect();
+        assert_eq!(
+            names,
+            [
+                "Roger Serradell",
+                "Marta Colomer",
+                "Mario Otero",
+                "Albert Cortés",
+                "Jordi Salas",
+                "Eva Coll"
+            ]
+        );
+        assert_eq!(
+            capuleto.narrators()[3].url().map(Url::as_str),
+            Some("https://www.audible.es/search?searchNarrator=Albert+Cort%C3%A9s")
+        );
+    }
+
+    #[test]
+    fn test_page_result_collect_runtime() {
+        let audiobooks = first_page().collect().unwrap();
+        assert!(audiobooks.iter().all(|book| book.runtime().is_some()));
+
+        // Duración: 12 horas y 34 mins
+        assert_eq!(
+            audiobooks[0].runtime(),
+            Some(Duration::from_secs(12 * 3600 + 34 * 60))
+        );
+
+        let json = serde_json::to_value(&audiobooks[0]).unwrap();
+        assert_eq!(
+            json["runtime"],
+            serde_json::json!({ "seconds": 45240, "text": "12 h 34 min" })
+        );
+
+        let audiobooks = last_page().collect().unwrap();
+        // Duración: 4 mins
+        assert!(audiobooks
+            .iter()
+            .any(|book| book.runtime() == Some(Duration::from_secs(4 * 60))));
+    }
+
+    #[test]
+    fn test_page_result_collect_series() {
+        let audiobooks = first_page().collect().unwrap();
+        assert_eq!(
+            audiobooks
+                .iter()
+                .filter(|book| !book.series().is_empty())
+                .count(),
+            15
+        );
+
+        let series = audiobooks[2].series();
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].name(), "Trilogía de Estocolmo");
+        assert_eq!(series[0].asin(), Some("B09SH6FSW6"));
+        assert_eq!(series[0].position(), Some("3"));
+        assert_eq!(
+            series[0].url().map(Url::as_str),
+            Some("https://www.audible.es/series/Trilogia-de-Estocolmo-Audiolibro/B09SH6FSW6")
+        );
+
+        let audiobooks = last_page().collect().unwrap();
+        // Serie: <a>Sherlock Holmes [Spanish Edition]</a>
+        let series = audiobooks
+            .iter()
+            .flat_map(|book| book.series())
+            .find(|series| series.name() == "Sherlock Holmes [Spanish Edition]")
+            .unwrap();
+        assert_eq!(series.position(), None);
+    }
+
+    #[test]
+    fn test_page_result_collect_series_positions() {
+        let series = label(
+            "seriesLabel",
+            r#"Series:
+                <a href="/series/Dune-Audiobooks/B002SZL0W6?ref=a_search">Dune</a>, Book 2,
+                <a href="/series/Dune-Chronicles-Audiobooks/B07B8HJ6QP">Dune Chronicles</a>, Book 2.5
+            "#,
+        );
+        let body = item("B002V8H59I", "Dune Messiah", &details(&series));
+        let url = Url::parse("https://www.audible.com/search").unwrap();
+        let audiobooks = PageResult::new(url, format!("<ul>{body}</ul>"))
+            .collect()
+            .unwrap();
+
+        let series = audiobooks[0].series();
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].name(), "Dune");
+        assert_eq!(series[0].position(), Some("2"));
+        assert_eq!(
+            series[0].url().map(Url::as_str),
+            Some("https://www.audible.com/series/Dune-Audiobooks/B002SZL0W6")
+        );
+        assert_eq!(series[1].name(), "Dune Chronicles");
+        assert_eq!(series[1].asin(), Some("B07B8HJ6QP"));
+        assert_eq!(series[1].position(), Some("2.5"));
+    }
+
+    #[test]
+    fn test_page_result_collect_ratings() {
+        let audiobooks = first_page().collect().unwrap();
+        // sin calificaciones
+        assert_eq!(audiobooks[0].rating(), None);
+        assert_eq!(
+            audiobooks
+                .iter()
+                .filter(|book| book.rating().is_none())
+                .count(),
+            15
+        );
+
+        let rated: Vec<Rating> = audiobooks.iter().filter_map(AudioBook::rating).collect();
+        assert_eq!(rated.len(), 35);
+        assert!(rated.iter().all(|rating| rating.count() > 0));
+        // 4.5 out of 5 stars, 166 calificaciones
+        assert!(rated.contains(&Rating::new(4.5, 166)));
+        // 5 out of 5 stars, 1 calificación
+        assert!(rated.contains(&Rating::new(5.0, 1)));
+    }
+
+    #[test]
+    fn test_page_result_collect_asin() {
+        let audiobooks = first_page().collect().unwrap();
+        assert_eq!(audiobooks[0].asin(), "8418107405");
+        assert_eq!(
+            audiobooks[0].url().map(Url::as_str),
+            Some("https://www.audible.es/pd/8418107405")
+        );
+
+        // Books are identified by their ASIN, so collecting a page twice yields no duplicates
+        let unique: HashSet<AudioBook> = audiobooks
+            .iter()
+            .chain(first_page().collect().unwrap().iter())
+            .cloned()
+            .collect();
+        assert_eq!(unique.len(), audiobooks.len());
+        assert!(unique.contains(&audiobooks[0]));
+    }
+
+    #[test]
+    fn test_page_result_collect_prices() {
+        let audiobooks = first_page().collect().unwrap();
+        assert!(audiobooks
+            .iter()
+            .all(|book| book.prices().regular().is_some()));
+
+        // Precio habitual: 18,99 €, the member price and credit cost are hidden
+        let prices = audiobooks[0].prices();
+        assert_eq!(prices.regular(), Some(&Price::new(1899, "EUR")));
+        assert_eq!(prices.sale(), None);
+        assert_eq!(prices.member(), None);
+        assert_eq!(prices.credits(), None);
+    }
+
+    #[test]
+    fn test_page_result_collect_visible_prices() {
+        let prices = r#"<div class="adblBuyBoxPrice">
+              <p class="bc-text buybox-regular-price">
+                <span class="bc-text">Regular price:</span>
+                <span class="bc-text">$1,234.99</span>
+                <span class="bc-text adblBuyBoxCreditCost">or 1 credit</span>
+              </p>
+              <p class="bc-text buybox-sale-price">
+                <span class="bc-text">Sale price:</span>
+                <span class="bc-text">$9.99</span>
+              </p>
+              <p class="bc-text buybox-member-price">
+                <span class="bc-text">Member price:</span>
+                <span class="bc-text">$17.49</span>
+              </p>
+            </div>"#;
+        let body = item("B002V1OF70", "Dune", &(details("") + prices));
+        let url = Url::parse("https://www.audible.com/search").unwrap();
+        let audiobooks = PageResult::new(url, format!("<ul>{body}</ul>"))
+            .collect()
+            .unwrap();
+
+        let prices = audiobooks[0].prices();
+        assert_eq!(prices.regular(), Some(&Price::new(123499, "USD")));
+        assert_eq!(prices.sale(), Some(&Price::new(999, "USD")));
+        assert_eq!(prices.member(), Some(&Price::new(1749, "USD")));
+        assert_eq!(prices.credits(), Some(1));
+    }
+
+    #[test]
+    fn test_page_result_collect_subtitle_covers_and_kind() {
+        let audiobooks = first_page().collect().unwrap();
+        assert!(audiobooks
+            .iter()
+            .all(|book| book.kind() == ProductKind::Audiobook));
+        assert_eq!(
+            audiobooks
+                .iter()
+                .filter(|book| book.subtitle().is_some())
+                .count(),
+            24
+        );
+
+        assert_eq!(audiobooks[0].subtitle(), None);
+        assert_eq!(audiobooks[1].subtitle(), Some("Trilogía de Estocolmo 2"));
+
+        let covers = audiobooks[0].covers();
+        assert_eq!(covers.len(), 2);
+        assert_eq!(covers[0].size(), Some(250));
+        assert_eq!(
+            covers[0].url().as_str(),
+            "https://m.media-amazon.com/images/I/51iqmOAk5cL._SL250_.jpg"
+        );
+        assert_eq!(covers[1].size(), Some(500));
+        assert_eq!(
+            covers[1].url().as_str(),
+            "https://m.media-amazon.com/images/I/51iqmOAk5cL._SL500_.jpg"
+        );
+    }
+
+    #[test]
+    fn test_page_result_collect_product_kinds() {
+        let item = |href: &str, title: &str, narrator: &str, format: &str| {
+            let popover = format!(
+                r#"<div class="bc-popover-inner"><ul>
+                  <li class="bc-list-item bc-spacing-micro">{title}</li>
+                  <li class="bc-list-item">Narrated by: {narrator}</li>
+                  <li class="bc-list-item">English</li>
+                  <li class="bc-list-item">{format}</li>
+                </ul></div>"#
+            );
+            let link =
+                format!(r#"<li class="bc-list-item"><h3><a href="{href}">Item</a></h3></li>"#);
+            let asin = href.rsplit('/').next().unwrap();
+            item(asin, "Item", &(popover + &details(&link)))
+        };
+        let body = format!(
+            "<ul>{}{}{}{}{}</ul>",
+            item(
+                "/pd/Dune-Audiobook/B002V1OF70",
+                "Dune",
+                "Scott Brick",
+                "Unabridged Audiobook"
+            ),
+            item(
+                "/podcast/The-Rest-Is-History/B08JJPBJ5M",
+                "The Rest Is History",
+                "Tom Holland",
+                "Podcast"
+            ),
+            item(
+                "/pd/Sherlock-Chronicles/B07KKKL3MT",
+                "The Sherlock Chronicles",
+                "Full Cast",
+                "Audible Original"
+            ),
+            // Titles and credits must not change the kind
+            item(
+                "/pd/The-Podcast-Murders/B0000000001",
+                "The Podcast Murders",
+                "Audible Original Cast",
+                "Unabridged Audiobook"
+            ),
+            item(
+                "/pd/Making-an-Audible-Original/B0000000002",
+                "Making an Audible Original",
+                "Someone",
+                "Abridged Audiobook"
+            ),
+        );
+        let url = Url::parse("https://www.audible.com/search").unwrap();
+        let audiobooks = PageResult::new(url, body).collect().unwrap();
+
+        let kinds: Vec<ProductKind> = audiobooks.iter().map(AudioBook::kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                ProductKind::Audiobook,
+                ProductKind::Podcast,
+                ProductKind::AudibleOriginal,
+                ProductKind::Audiobook,
+                ProductKind::Audiobook
+            ]
+        );
+    }
+
+    #[test]
+    fn test_page_result_collect_lenient() {
+        let item = |asin: &str, button: &str, narrator: &str, release_date: &str| {
+            let content = format!(
+                "{button}<ul>{}{}{}</ul>",
+                label("narratorLabel", narrator),
+                label("releaseDateLabel", release_date),
+                label("languageLabel", "Español")
+            );
+            item(asin, &format!("Item {asin}"), &content)
+        };
+        let sample = SAMPLE_BUTTON;
+        let narrator = r#"<a href="/search?searchNarrator=Jordi+Salas">Jordi Salas</a>"#;
+        let body = format!(
+            "<ul>{}{}{}{}{}{}</ul>",
+            item(
+                "B000000001",
+                sample,
+                narrator,
+                "Fecha de lanzamiento: 30-07-20"
+            ),
+            item("B000000002", sample, "", "Fecha de lanzamiento: 30-07-20"),
+            item(
+                "B000000003",
+                sample,
+                narrator,
+                "Fecha de lanzamiento: 45-13-20"
+            ),
+            item("B000000004", "", narrator, "Fecha de lanzamiento: 30-07-20"),
+            item(
+                "B000000005",
+                r#"<button data-mp3="not a url"></button>"#,
+                narrator,
+                "Fecha de lanzamiento: 30-07-20"
+            ),
+            item("", sample, narrator, "Fecha de lanzamiento: 30-07-20"),
+        );
+        let url = Url::parse("https://www.audible.es/search").unwrap();
+        let page_result = PageResult::new(url, body);
+
+        let (audiobooks, diagnostics) = page_result.collect_lenient().unwrap();
+        let asins: Vec<&str> = audiobooks.iter().map(AudioBook::asin).collect();
+        assert_eq!(
+            asins,
+            [
+                "B000000001",
+                "B000000002",
+                "B000000003",
+                "B000000004",
+                "B000000005"
+            ]
+        );
+        assert!(audiobooks[1].narrators().is_empty());
+        assert_eq!(audiobooks[2].release_date(), None);
+        assert_eq!(audiobooks[3].sample_url(), None);
+        assert_eq!(audiobooks[4].sample_url(), None);
+
+        let reported: Vec<(usize, Option<&str>, &str, bool)> = diagnostics
+            .iter()
+            .map(|d| (d.index(), d.asin(), d.field(), d.skipped()))
+            .collect();
+        assert_eq!(
+            reported,
+            [
+                (1, Some("B000000002"), "narrators", false),
+                (2, Some("B000000003"), "releaseDate", false),
+                (4, Some("B000000005"), "sampleUrl", false),
+                (5, None, "asin", true),
+            ]
+        );
+        assert!(diagnostics[1].snippet().contains("45-13-20"));
+        assert!(diagnostics[2].snippet().starts_with("<button"));
+
+        let err = page_result.collect().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Cannot parse narrators: item 1 (B000000002): Narrator not found"
+        );
+    }
+
+    #[test]
+    fn test_page_result_kind() {
+        assert_eq!(first_page().kind(), PageKind::Results);
+
+        let url = Url::parse("https://www.audible.es/search?keywords=xyzzy").unwrap();
+        let kind = |body: &str| PageResult::new(url.clone(), body.to_string()).kind();
+        assert_eq!(kind(CAPTCHA_PAGE), PageKind::Blocked);
+        assert_eq!(
+            kind("<html><head><title>Robot Check</title></head><body></body></html>"),
+            PageKind::Blocked
+        );
+        assert_eq!(kind(NO_RESULTS_PAGE), PageKind::NoResults);
+        assert_eq!(
+            kind(r#"<h2 class="bc-heading">No results for "xyzzy"</h2>"#),
+            PageKind::NoResults
+        );
+        assert_eq!(
+            kind(r#"<h1 class="bc-heading">「xyzzy」の検索結果はありません</h1>"#),
+            PageKind::NoResults
+        );
+        assert_eq!(
+            kind(r#"<span class="resultsSummarySubheading">0 resultados</span>"#),
+            PageKind::NoResults
+        );
+        assert_eq!(kind("<ul></ul>"), PageKind::UnexpectedLayout);
+        assert_eq!(
+            kind(r#"<h1>Lo sentimos, se ha producido un error</h1>"#),
+            PageKind::UnexpectedLayout
+        );
+    }
+
+    #[test]
+    fn test_page_result_collect_not_results() {
+        let url = Url::parse("https://www.audible.es/search?keywords=xyzzy").unwrap();
+        let page_result = |body: &str| PageResult::new(url.clone(), body.to_string());
+
+        let no_results = page_result(NO_RESULTS_PAGE);
+        let (audiobooks, diagnostics) = no_results.collect_lenient().unwrap();
+        assert!(audiobooks.is_empty());
+        assert!(diagnostics.is_empty());
+        assert!(no_results.collect().unwrap().is_empty());
+
+        let blocked = page_result(CAPTCHA_PAGE);
+        assert!(matches!(
+            blocked.collect_lenient(),
+            Err(Error::Blocked { .. })
+        ));
+        assert!(matches!(blocked.collect(), Err(Error::Blocked { .. })));
+
+        let unexpected = page_result("<ul></ul>");
+        assert!(matches!(
+            unexpected.collect_lenient(),
+            Err(Error::UnexpectedLayout { .. })
+        ));
+        assert!(matches!(
+            unexpected.collect(),
+            Err(Error::UnexpectedLayout { .. })
+        ));
+    }
+
+    #[test]
+    fn test_page_result_facets() {
+        let facets = first_page().facets();
+        let keys: Vec<&str> = facets.iter().map(Facet::key).collect();
+        assert_eq!(
+            keys,
+            [
+                "node",
+                "publication_date",
+                "feature_seven_browse-bin",
+                "feature_six_browse-bin",
+                "feature_twenty_browse-bin",
+                "feature_twelve_browse-bin",
+                "feature_nine_browse-bin",
+                "feature_twenty-two_browse-bin",
+                "sort",
+                "pageSize",
+            ]
+        );
+
+        let categories = &facets[0];
+        assert_eq!(categories.name(), "Categorías");
+        assert_eq!(categories.options().len(), 19);
+        let teens = &categories.options()[0];
+        assert_eq!(teens.label(), "Adolescentes");
+        assert_eq!(teens.value(), "18059300031");
+        assert_eq!(
+            teens.url().map(Url::as_str),
+            Some("https://www.audible.es/search?node=18059300031&pageSize=50&searchNarrator=Jordi+Salas&sort=title-asc-rank")
+        );
+
+        let release_date = &facets[1];
+        assert_eq!(release_date.name(), "Nuevas publicaciones");
+        let labels: Vec<&str> = release_date
+            .options()
+            .iter()
+            .map(FacetOption::label)
+            .collect();
+        assert_eq!(labels, ["Últimos 30 días", "Últimos 90 días"]);
+        assert_eq!(release_date.selected(), None);
+
+        let length = &facets[2];
+        assert_eq!(length.name(), "Duración");
+        assert_eq!(length.options()[0].label(), "Menos de 1 hora");
+        assert_eq!(length.options()[0].value(), "18385624031");
+
+        let sort = &facets[8];
+        assert_eq!(sort.name(), "Ordenar por");
+        assert_eq!(sort.options().len(), 10);
+        assert_eq!(
+            sort.selected().map(FacetOption::value),
+            Some("title-asc-rank")
+        );
+
+        let page_size = &facets[9];
+        assert_eq!(page_size.name(), "Mostrar");
+        let values: Vec<&str> = page_size.options().iter().map(FacetOption::value).collect();
+        assert_eq!(values, ["", "30", "40", "50"]);
+        assert_eq!(page_size.selected().map(FacetOption::label), Some("50"));
+
+        let params = QueryParams::new().narrator("Jordi Salas");
+        let url = length.options()[0].apply(params).build_url().unwrap();
+        assert_eq!(
+            url.as_str(),
+            "https://www.audible.es/search?searchNarrator=Jordi+Salas&feature_seven_browse-bin=18385624031&sort=title-asc-rank&pageSize=50&page=1"
+        );
+    }
+
+    #[test]
+    fn test_page_result_marketplace_from_url() {
+        let url = Url::parse("https://www.audible.co.jp/search?keywords=dune").unwrap();
+        let page_result = PageResult::new(url, String::new());
+        assert_eq!(page_result.marketplace(), Marketplace::Jp);
+
+        let url = Url::parse("https://example.com").unwrap();
+        let page_result = PageResult::new(url, String::new());
+        assert_eq!(page_result.marketplace(), Marketplace::Es);
+    }
+
+    #[test]
+    fn test_page_result_collect_marketplaces() {
+        let fixtures = [
+            (Marketplace::Us, "Release date: 07-30-20"),
+            (Marketplace::Uk, "Release date: 30-07-20"),
+            (Marketplace::De, "Erscheinungsdatum: 30.07.2020"),
+            (Marketplace::Fr, "Date de publication: 30/07/2020"),
+            (Marketplace::It, "Data di uscita: 30/07/2020"),
+            (Marketplace::Es, "Fecha de lanzamiento: 30-07-20"),
+            (Marketplace::Jp, "配信日: 2020/07/30"),
+            (Marketplace::Au, "Release date: 30-07-20"),
+            (Marketplace::Ca, "Release date: 07-30-20"),
+            (Marketplace::In, "Release date: 30-07-20"),
+        ];
+
+        for (marketplace, release_date) in fixtures {
+            let url = marketplace.base_url().join("search").unwrap();
+            let body = item(
+                "B002V1OF70",
+                "Dune",
+                &details(&label("releaseDateLabel", release_date)),
+            );
+            let page_result = PageResult::new(url, format!("<ul>{body}</ul>"));
+            assert_eq!(page_result.marketplace(), marketplace);
+
+            let audiobooks = page_result.collect().unwrap();
+            assert_eq!(audiobooks.len(), 1, "{marketplace}");
+            assert_eq!(
+                audiobooks[0].url(),
+                Some(&marketplace.product_url("B002V1OF70")),
+                "{marketplace}"
+            );
+            assert_eq!(
+                audiobooks[0].release_date(),
+                NaiveDate::from_ymd_opt(2020, 7, 30),
+                "{marketplace}"
+            );
+        }
+    }
+
     #[test]
     fn test_page_result_first() {
         let url = Url::parse("https://example.com").unwrap();
@@ -236,6 +1566,86 @@ mod tests {
         assert!(!page_result.has_prev());
         assert!(page_result.prev_page_url().is_none());
         assert_eq!(page_result.page(), 1);
+        assert_eq!(page_result.total_results(), Some(113));
+        assert_eq!(page_result.total_pages(), Some(3));
+        assert_eq!(page_result.page_size(), Some(50));
+    }
+
+    #[test]
+    fn test_page_result_page_urls() {
+        let urls: Vec<String> = first_page()
+            .page_urls()
+            .unwrap()
+            .iter()
+            .map(Url::to_string)
+            .collect();
+        assert_eq!(
+            urls,
+            [
+                "https://www.audible.es/search?searchNarrator=Jordi+Salas&sort=title-asc-rank&pageSize=50&page=1",
+                "https://www.audible.es/search?searchNarrator=Jordi+Salas&sort=title-asc-rank&pageSize=50&page=2",
+                "https://www.audible.es/search?searchNarrator=Jordi+Salas&sort=title-asc-rank&pageSize=50&page=3",
+            ]
+        );
+
+        let page_result = PageResult::new(
+            Url::parse("https://www.audible.es/search").unwrap(),
+            String::new(),
+        );
+        assert_eq!(page_result.total_results(), None);
+        assert_eq!(page_result.page_urls(), None);
+    }
+
+    #[test]
+    fn test_page_result_inconsistent_summary() {
+        let url = Url::parse("https://www.audible.es/search").unwrap();
+        let page_result = |summary: &str, page: u32| {
+            let body = format!(
+                r#"<span class="resultsSummarySubheading">{summary}</span>
+                <a class="pageNumberElement">1</a>
+                <span class="pageNumberElement">{page}</span>
+                <a class="pageNumberElement">6</a>"#
+            );
+            PageResult::new(url.clone(), body)
+        };
+
+        // Falls back to the pagination links rather than overflowing
+        let second = page_result("0 - 20 de 113 resultados", 2);
+        assert_eq!(second.page(), 2);
+        assert_eq!(second.total_results(), Some(113));
+        assert_eq!(second.total_pages(), Some(6));
+
+        let first = page_result("30 - 10 de 113 resultados", 1);
+        assert_eq!(first.total_pages(), Some(6));
+    }
+
+    #[test]
+    fn test_parse_results_summary() {
+        assert_eq!(
+            parse_results_summary("1 - 50 de 113 resultados"),
+            Some((1, 50, 113))
+        );
+        assert_eq!(
+            parse_results_summary("101 - 113 de 113 resultados"),
+            Some((101, 113, 113))
+        );
+        assert_eq!(
+            parse_results_summary("21-40 of 1,234 results"),
+            Some((21, 40, 1234))
+        );
+        assert_eq!(
+            parse_results_summary("1 - 20 von 1.234 Ergebnissen"),
+            Some((1, 20, 1234))
+        );
+        assert_eq!(
+            parse_results_summary("1 - 20 sur 1\u{202f}234 résultats"),
+            Some((1, 20, 1234))
+        );
+        assert_eq!(
+            parse_results_summary("1,234 件中 1 - 20 件"),
+            Some((1, 20, 1234))
+        );
+        assert_eq!(parse_results_summary("No hay resultados"), None);
     }
 
     #[test]
@@ -249,5 +1659,7 @@ mod tests {
         assert!(page_result.has_prev());
         assert!(page_result.prev_page_url().is_some());
         assert_eq!(page_result.page(), 3);
+        assert_eq!(page_result.total_results(), Some(113));
+        assert_eq!(page_result.total_pages(), Some(3));
     }
 }
@@ -1,65 +1,775 @@
-use url::{ParseError, Url};
+use serde::{Deserialize, Serialize};
+use url::Url;
 
-#[derive(Default, Clone)]
+use crate::filters::Filter;
+use crate::page_result::TRACKING_PARAMS;
+use crate::{
+    Error, Facet, FacetOption, Language, Length, Marketplace, ReleasedWithin, Result, SortOrder,
+};
+
+/// Number of results per page Audible serves when the `pageSize` parameter is absent or empty.
+const DEFAULT_PAGE_SIZE: u32 = 20;
+
+/// The parameters of an Audible search.
+///
+/// # Serialization
+///
+/// Searches can be saved to and loaded from files with camelCase field names. Missing fields
+/// take the defaults of [`QueryParams::new`], so a saved search only needs what it filters by:
+///
+/// ```
+/// use audible_scraper::{Length, QueryParams};
+///
+/// let params: QueryParams = toml::from_str(r#"
+///     narrator = "Jordi Salas"
+///     length = "3-6h"
+/// "#).unwrap();
+///
+/// assert_eq!(params, QueryParams::new().narrator("Jordi Salas").length(Length::ThreeToSixHours));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default = "QueryParams::new")]
 pub struct QueryParams {
-    narrator: Option<String>,
+    marketplace: Marketplace,
+    #[serde(skip_serializing_if = "Option::is_none")]
     keywords: Option<String>,
-    sort: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    author: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    narrator: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    publisher: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    category: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    released_within: Option<ReleasedWithin>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<Language>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    length: Option<Length>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    refinements: Vec<(String, String)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sort: Option<SortOrder>,
     page_size: u32,
     page: u32,
 }
 
+impl Default for QueryParams {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[allow(dead_code)]
 impl QueryParams {
     pub fn new() -> Self {
         Self {
+            marketplace: Marketplace::default(),
+            keywords: None,
+            title: None,
+            author: None,
+            narrator: None,
+            publisher: None,
+            category: None,
+            released_within: None,
+            language: None,
+            length: None,
+            refinements: Vec::new(),
+            sort: Some(SortOrder::TitleAsc),
             page_size: 50,
-            sort: "title-asc-rank".to_string(),
             page: 1,
-            ..Default::default()
         }
     }
 
+    /// Parses the parameters of an Audible search URL, e.g. one copied from the browser.
+    ///
+    /// The marketplace is taken from the host, every supported query pair is recognised and
+    /// any other pair, except the tracking ones, is kept as a [`refinement`](Self::refinement)
+    /// so that [`build_url`](Self::build_url) returns the same search. A missing `sort` keeps
+    /// Audible's default order and a missing `pageSize` means 20 results per page.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Parse`] if the host is not an Audible marketplace or a sort order,
+    /// page size or page number cannot be parsed, and an [`Error::InvalidPageSize`] if the page
+    /// size is not offered by Audible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use audible_scraper::{Marketplace, QueryParams, SortOrder};
+    /// use url::Url;
+    ///
+    /// let url = Url::parse("https://www.audible.co.uk/search?searchNarrator=Stephen+Fry&sort=review-rank").unwrap();
+    /// let params = QueryParams::from_url(&url).unwrap();
+    ///
+    /// assert_eq!(params.get_marketplace(), Marketplace::Uk);
+    /// assert_eq!(params.get_sort(), Some(SortOrder::Rating));
+    /// ```
+    pub fn from_url(url: &Url) -> Result<Self> {
+        let marketplace = Marketplace::from_url(url).ok_or_else(|| {
+            Error::parse("marketplace", format!("{url} is not an Audible search URL"))
+        })?;
+        let mut params = Self {
+            marketplace,
+            sort: None,
+            page_size: DEFAULT_PAGE_SIZE,
+            ..Self::new()
+        };
+
+        for (key, value) in url.query_pairs() {
+            let text = (!value.is_empty()).then(|| value.to_string());
+            match key.as_ref() {
+                "keywords" => params.keywords = text,
+                "title" => params.title = text,
+                "searchAuthor" => params.author = text,
+                "searchNarrator" => params.narrator = text,
+                "searchProvider" => params.publisher = text,
+                "node" => params.category = text,
+                "sort" if value.is_empty() => params.sort = None,
+                "sort" => params.sort = Some(value.parse()?),
+                "pageSize" if value.is_empty() => params.page_size = DEFAULT_PAGE_SIZE,
+                "pageSize" => {
+                    params.page_size = check_page_size(parse_number("pageSize", &value)?)?
+                }
+                "page" => params.page = parse_number("page", &value)?,
+                ReleasedWithin::KEY if ReleasedWithin::from_id(&value, marketplace).is_some() => {
+                    params.released_within = ReleasedWithin::from_id(&value, marketplace)
+                }
+                Language::KEY if Language::from_id(&value, marketplace).is_some() => {
+                    params.language = Language::from_id(&value, marketplace)
+                }
+                Length::KEY if Length::from_id(&value, marketplace).is_some() => {
+                    params.length = Length::from_id(&value, marketplace)
+                }
+                key if TRACKING_PARAMS.contains(&key) => {}
+                key => params
+                    .refinements
+                    .push((key.to_string(), value.into_owned())),
+            }
+        }
+
+        Ok(params)
+    }
+
+    /// Storefront to search in, defaults to [`Marketplace::Es`].
+    pub fn marketplace(mut self, marketplace: Marketplace) -> Self {
+        self.marketplace = marketplace;
+        self
+    }
+
+    pub fn get_marketplace(&self) -> Marketplace {
+        self.marketplace
+    }
+
+    /// Whether the search has any of the keywords, title, author, narrator or publisher, without
+    /// which it returns the whole storefront.
+    pub fn has_search_terms(&self) -> bool {
+        [
+            &self.keywords,
+            &self.title,
+            &self.author,
+            &self.narrator,
+            &self.publisher,
+        ]
+        .iter()
+        .any(|field| field.is_some())
+    }
+
+    /// Free text search, equivalent to the search box (`keywords`).
     pub fn keywords(mut self, keywords: &str) -> Self {
         self.keywords = Some(keywords.to_string());
         self
     }
 
+    /// Restricts the search to titles matching the given text (`title`).
+    pub fn title(mut self, title: &str) -> Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// Restricts the search to the given author (`searchAuthor`).
+    pub fn author(mut self, author: &str) -> Self {
+        self.author = Some(author.to_string());
+        self
+    }
+
+    /// Restricts the search to the given narrator (`searchNarrator`).
     pub fn narrator(mut self, narrator: &str) -> Self {
         self.narrator = Some(narrator.to_string());
         self
     }
 
-    pub fn sort(mut self, sort: &str) -> Self {
-        self.sort = sort.to_string();
+    /// Restricts the search to the given publisher (`searchProvider`).
+    pub fn publisher(mut self, publisher: &str) -> Self {
+        self.publisher = Some(publisher.to_string());
+        self
+    }
+
+    /// Restricts the search to a category, given by its browse node id (`node`), e.g.
+    /// `"18059300031"`. The categories of a search are listed by its facets.
+    pub fn category(mut self, node: &str) -> Self {
+        self.category = Some(node.to_string());
+        self.remove_refinement("node");
+        self
+    }
+
+    /// Restricts the search to the audiobooks released in a recent period (`publication_date`).
+    pub fn released_within(mut self, released_within: ReleasedWithin) -> Self {
+        self.released_within = Some(released_within);
+        self.remove_refinement(ReleasedWithin::KEY);
+        self
+    }
+
+    /// Restricts the search to the audiobooks narrated in a language (`feature_six_browse-bin`).
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = Some(language);
+        self.remove_refinement(Language::KEY);
+        self
+    }
+
+    /// Restricts the search to the audiobooks of a length bucket (`feature_seven_browse-bin`).
+    pub fn length(mut self, length: Length) -> Self {
+        self.length = Some(length);
+        self.remove_refinement(Length::KEY);
         self
     }
 
+    /// Refines the search with a filter of the results page, e.g. `("node", "18059300031")` for
+    /// a category. Setting a key again replaces its previous value, including the one of the
+    /// typed filter with the same key such as [`category`](Self::category).
+    ///
+    /// See [`PageResult::facets`](crate::PageResult::facets) for the refinements a search offers.
+    pub fn refinement(mut self, key: &str, value: &str) -> Self {
+        match key {
+            "node" => self.category = None,
+            ReleasedWithin::KEY => self.released_within = None,
+            Language::KEY => self.language = None,
+            Length::KEY => self.length = None,
+            _ => {}
+        }
+        self.remove_refinement(key);
+        self.refinements.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    fn remove_refinement(&mut self, key: &str) {
+        self.refinements.retain(|(known, _)| known != key);
+    }
+
+    /// Returns the parameters with the typed filters whose refinement id is not known on the
+    /// marketplace replaced by the matching refinements of `facets`, usually those of the same
+    /// search without these filters, see [`without_filters`](Self::without_filters).
+    ///
+    /// The refinement ids found in `facets` are remembered for the marketplace, so the filters
+    /// of the later searches resolve without facets.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedFilter`] if a filter was not found before and matches none of
+    /// the options of `facets`.
+    pub fn resolve_filters(self, facets: &[Facet]) -> Result<Self> {
+        let marketplace = self.marketplace;
+        let mut params = self.clone();
+        if let Some(filter) = self.released_within.filter(|f| f.id(marketplace).is_none()) {
+            let option = filter.find(facets);
+            let id = resolve_id(Filter::ReleasedWithin(filter), marketplace, option)?;
+            params = params.refinement(ReleasedWithin::KEY, &id);
+        }
+        if let Some(filter) = self.language.filter(|f| f.id(marketplace).is_none()) {
+            let option = filter.find(facets);
+            let id = resolve_id(Filter::Language(filter), marketplace, option)?;
+            params = params.refinement(Language::KEY, &id);
+        }
+        if let Some(filter) = self.length.filter(|f| f.id(marketplace).is_none()) {
+            let option = filter.find(facets);
+            let id = resolve_id(Filter::Length(filter), marketplace, option)?;
+            params = params.refinement(Length::KEY, &id);
+        }
+
+        Ok(params)
+    }
+
+    /// Returns the parameters without the release date, language and length filters.
+    pub fn without_filters(&self) -> Self {
+        Self {
+            released_within: None,
+            language: None,
+            length: None,
+            ..self.clone()
+        }
+    }
+
+    /// Order of the results, defaults to [`SortOrder::TitleAsc`].
+    pub fn sort(mut self, sort: SortOrder) -> Self {
+        self.sort = Some(sort);
+        self
+    }
+
+    /// Order of the results, `None` for the default order of Audible.
+    pub fn get_sort(&self) -> Option<SortOrder> {
+        self.sort
+    }
+
     pub fn page(mut self, page: u32) -> Self {
         self.page = page;
         self
     }
 
+    /// Number of results per page, one of [`Marketplace::PAGE_SIZES`]; defaults to 50.
+    ///
+    /// Other values are rejected by [`build_url`](Self::build_url).
     pub fn page_size(mut self, page_size: u32) -> Self {
         self.page_size = page_size;
         self
     }
 
-    pub fn build_url(&self) -> Result<Url, ParseError> {
-        let mut base_url = Url::parse("https://www.audible.es/search?")?;
+    pub fn get_page_size(&self) -> u32 {
+        self.page_size
+    }
+
+    /// Builds the URL of the search.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidPageSize`] if the page size is not offered by Audible,
+    /// and [`Error::UnsupportedFilter`] if a typed filter has no known refinement id on the
+    /// marketplace; resolve it from the facets of the search with
+    /// [`resolve_filters`](Self::resolve_filters) first, as the [`Scraper`](crate::Scraper)
+    /// does.
+    pub fn build_url(&self) -> Result<Url> {
+        let marketplace = self.marketplace;
+        check_page_size(self.page_size)?;
 
-        if let Some(ref narrator) = self.narrator {
-            base_url
-                .query_pairs_mut()
-                .append_pair("searchNarrator", narrator);
+        let typed_filters = [
+            self.released_within.map(|filter| {
+                (
+                    ReleasedWithin::KEY,
+                    filter.id(marketplace),
+                    "released within",
+                )
+            }),
+            self.language
+                .map(|filter| (Language::KEY, filter.id(marketplace), "language")),
+            self.length
+                .map(|filter| (Length::KEY, filter.id(marketplace), "length")),
+        ];
+        let mut filters = Vec::new();
+        for (key, id, filter) in typed_filters.into_iter().flatten() {
+            let id = id.ok_or(Error::UnsupportedFilter {
+                filter,
+                marketplace,
+            })?;
+            filters.push((key, id));
         }
 
-        base_url
-            .query_pairs_mut()
-            .append_pair("sort", &self.sort)
-            .append_pair("pageSize", &self.page_size.to_string())
-            .append_pair("page", &self.page.to_string());
+        let mut base_url = self.marketplace.base_url().join("search?")?;
+
+        {
+            let mut pairs = base_url.query_pairs_mut();
+
+            let text_fields = [
+                ("keywords", &self.keywords),
+                ("title", &self.title),
+                ("searchAuthor", &self.author),
+                ("searchNarrator", &self.narrator),
+                ("searchProvider", &self.publisher),
+            ];
+
+            for (key, value) in text_fields {
+                if let Some(value) = value {
+                    pairs.append_pair(key, value);
+                }
+            }
+
+            if let Some(node) = &self.category {
+                pairs.append_pair("node", node);
+            }
+
+            for (key, value) in filters {
+                pairs.append_pair(key, value);
+            }
+
+            for (key, value) in &self.refinements {
+                pairs.append_pair(key, value);
+            }
+
+            if let Some(sort) = self.sort {
+                pairs.append_pair("sort", sort.as_str());
+            }
+
+            pairs
+                .append_pair("pageSize", &self.page_size.to_string())
+                .append_pair("page", &self.page.to_string());
+        }
 
         Ok(base_url)
     }
 }
+
+impl TryFrom<&str> for QueryParams {
+    type Error = Error;
+
+    /// Parses an Audible search URL, see [`QueryParams::from_url`].
+    fn try_from(url: &str) -> Result<Self> {
+        Self::from_url(&Url::parse(url.trim())?)
+    }
+}
+
+fn parse_number(field: &str, value: &str) -> Result<u32> {
+    value
+        .parse()
+        .map_err(|_| Error::parse(field, format!("{value:?} is not a valid number")))
+}
+
+/// Returns `page_size` if it is one of [`Marketplace::PAGE_SIZES`].
+/// Returns the refinement id of `filter` found before on `marketplace`, or else the value of
+/// `option`, which is then remembered.
+fn resolve_id(
+    filter: Filter,
+    marketplace: Marketplace,
+    option: Option<&FacetOption>,
+) -> Result<String> {
+    if let Some(id) = filter.found_id(marketplace) {
+        return Ok(id);
+    }
+    let option = option.ok_or(Error::UnsupportedFilter {
+        filter: filter.name(),
+        marketplace,
+    })?;
+    filter.remember(marketplace, option.value());
+    Ok(option.value().to_string())
+}
+
+fn check_page_size(page_size: u32) -> Result<u32> {
+    if Marketplace::PAGE_SIZES.contains(&page_size) {
+        Ok(page_size)
+    } else {
+        Err(Error::InvalidPageSize {
+            page_size,
+            allowed: &Marketplace::PAGE_SIZES,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_url_defaults() {
+        let url = QueryParams::new().build_url().unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://www.audible.es/search?sort=title-asc-rank&pageSize=50&page=1"
+        );
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(QueryParams::default(), QueryParams::new());
+        assert!(QueryParams::default().build_url().is_ok());
+    }
+
+    #[test]
+    fn test_has_search_terms() {
+        assert!(!QueryParams::new().has_search_terms());
+        assert!(!QueryParams::new()
+            .category("18059298031")
+            .has_search_terms());
+        assert!(QueryParams::new()
+            .publisher("Audible Studios")
+            .has_search_terms());
+    }
+
+    #[test]
+    fn test_build_url_keywords() {
+        let url = QueryParams::new().keywords("dune").build_url().unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://www.audible.es/search?keywords=dune&sort=title-asc-rank&pageSize=50&page=1"
+        );
+    }
+
+    #[test]
+    fn test_build_url_refinements() {
+        let url = QueryParams::new()
+            .narrator("Jordi Salas")
+            .refinement("node", "18059300031")
+            .refinement("feature_six_browse-bin", "18385686031")
+            .refinement("node", "18059288031")
+            .build_url()
+            .unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://www.audible.es/search?searchNarrator=Jordi+Salas&feature_six_browse-bin=18385686031&node=18059288031&sort=title-asc-rank&pageSize=50&page=1"
+        );
+    }
+
+    #[test]
+    fn test_build_url_sort() {
+        let url = QueryParams::new()
+            .keywords("dune")
+            .sort(SortOrder::ReleaseDateDesc)
+            .build_url()
+            .unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://www.audible.es/search?keywords=dune&sort=pubdate-desc-rank&pageSize=50&page=1"
+        );
+    }
+
+    #[test]
+    fn test_build_url_filters() {
+        let url = QueryParams::new()
+            .keywords("thriller")
+            .category("18059298031")
+            .released_within(ReleasedWithin::Last30Days)
+            .language(Language::Spanish)
+            .length(Length::ThreeToSixHours)
+            .build_url()
+            .unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://www.audible.es/search?keywords=thriller&node=18059298031&publication_date=18385621031&feature_six_browse-bin=18385686031&feature_seven_browse-bin=18385626031&sort=title-asc-rank&pageSize=50&page=1"
+        );
+    }
+
+    #[test]
+    fn test_build_url_unsupported_filter() {
+        let err = QueryParams::new()
+            .marketplace(Marketplace::Us)
+            .keywords("thriller")
+            .length(Length::UnderOneHour)
+            .build_url()
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            Error::UnsupportedFilter {
+                filter: "length",
+                marketplace: Marketplace::Us
+            }
+        ));
+    }
+
+    #[test]
+    fn test_from_url_round_trip() {
+        let params = QueryParams::new()
+            .keywords("thriller")
+            .author("Dolores Redondo")
+            .narrator("Jordi Salas")
+            .category("18059298031")
+            .released_within(ReleasedWithin::Last30Days)
+            .language(Language::Spanish)
+            .length(Length::ThreeToSixHours)
+            .refinement("feature_nine_browse-bin", "18385634031")
+            .sort(SortOrder::ReleaseDateDesc)
+            .page_size(30)
+            .page(2);
+
+        let url = params.build_url().unwrap();
+        assert_eq!(QueryParams::from_url(&url).unwrap(), params);
+
+        let params = QueryParams::new()
+            .marketplace(Marketplace::Jp)
+            .title("火花");
+        let url = params.build_url().unwrap();
+        assert_eq!(QueryParams::try_from(url.as_str()).unwrap(), params);
+    }
+
+    #[test]
+    fn test_from_url_browser() {
+        let url = "https://www.audible.es/search?keywords=&node=18059298031&pageSize=50\
+            &searchNarrator=Jordi+Salas&feature_seven_browse-bin=18385627031\
+            &feature_twenty_browse-bin=21495814031&ref=a_search_l1_catRefs_3\
+            &pf_rd_p=efe449c8-5d6d-4dbe-b435-bed4c883478f&pageLoadId=QWUtE946ovrljGaj";
+        let params = QueryParams::try_from(url).unwrap();
+
+        assert_eq!(params.get_marketplace(), Marketplace::Es);
+        assert_eq!(params.get_sort(), None);
+        assert_eq!(params.length, Some(Length::SixToTenHours));
+        assert_eq!(
+            params.refinements,
+            [(
+                String::from("feature_twenty_browse-bin"),
+                String::from("21495814031")
+            )]
+        );
+        assert_eq!(
+            params.build_url().unwrap().as_str(),
+            "https://www.audible.es/search?searchNarrator=Jordi+Salas&node=18059298031&feature_seven_browse-bin=18385627031&feature_twenty_browse-bin=21495814031&pageSize=50&page=1"
+        );
+
+        let params = QueryParams::try_from("https://www.audible.com/search?keywords=dune").unwrap();
+        assert_eq!(params.get_marketplace(), Marketplace::Us);
+        assert_eq!(
+            params.build_url().unwrap().as_str(),
+            "https://www.audible.com/search?keywords=dune&pageSize=20&page=1"
+        );
+    }
+
+    #[test]
+    fn test_from_url_errors() {
+        let err = QueryParams::try_from("https://example.com/search?keywords=dune").unwrap_err();
+        assert!(matches!(err, Error::Parse { field, .. } if field == "marketplace"));
+
+        let err =
+            QueryParams::try_from("https://www.audible.es/search?sort=relevance").unwrap_err();
+        assert!(matches!(err, Error::Parse { field, .. } if field == "sort order"));
+
+        let err = QueryParams::try_from("https://www.audible.es/search?page=two").unwrap_err();
+        assert!(matches!(err, Error::Parse { field, .. } if field == "page"));
+
+        let err = QueryParams::try_from("not a url").unwrap_err();
+        assert!(matches!(err, Error::Url(_)));
+    }
+
+    #[test]
+    fn test_build_url_page_size() {
+        for page_size in [20, 30, 40, 50] {
+            let url = QueryParams::new()
+                .keywords("dune")
+                .page_size(page_size)
+                .build_url()
+                .unwrap();
+            assert!(url.as_str().contains(&format!("pageSize={page_size}")));
+        }
+
+        for page_size in [0, 25, 100] {
+            let err = QueryParams::new()
+                .keywords("dune")
+                .page_size(page_size)
+                .build_url()
+                .unwrap_err();
+            assert!(
+                matches!(err, Error::InvalidPageSize { page_size: size, .. } if size == page_size)
+            );
+        }
+
+        let err = QueryParams::try_from("https://www.audible.es/search?pageSize=25").unwrap_err();
+        assert!(matches!(err, Error::InvalidPageSize { page_size: 25, .. }));
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let params = QueryParams::new()
+            .marketplace(Marketplace::Uk)
+            .keywords("thriller")
+            .narrator("Stephen Fry")
+            .category("18059298031")
+            .refinement("feature_nine_browse-bin", "18385634031")
+            .sort(SortOrder::Popularity)
+            .page_size(20);
+
+        let toml = toml::to_string(&params).unwrap();
+        assert_eq!(
+            toml,
+            r#"marketplace = "uk"
+keywords = "thriller"
+narrator = "Stephen Fry"
+category = "18059298031"
+refinements = [["feature_nine_browse-bin", "18385634031"]]
+sort = "popularity-rank"
+pageSize = 20
+page = 1
+"#
+        );
+        assert_eq!(toml::from_str::<QueryParams>(&toml).unwrap(), params);
+
+        let json = serde_json::to_string(&params).unwrap();
+        assert_eq!(serde_json::from_str::<QueryParams>(&json).unwrap(), params);
+    }
+
+    #[test]
+    fn test_serde_defaults() {
+        let params: QueryParams = serde_json::from_str(r#"{"keywords": "dune"}"#).unwrap();
+        assert_eq!(params, QueryParams::new().keywords("dune"));
+
+        let err = serde_json::from_str::<QueryParams>(r#"{"sort": "relevance"}"#).unwrap_err();
+        assert!(err.to_string().contains("unknown variant `relevance`"));
+    }
+
+    #[test]
+    fn test_build_url_encodes_values() {
+        let url = QueryParams::new()
+            .narrator("Jordi Salas")
+            .publisher("Penguin & Co")
+            .build_url()
+            .unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://www.audible.es/search?searchNarrator=Jordi+Salas&searchProvider=Penguin+%26+Co&sort=title-asc-rank&pageSize=50&page=1"
+        );
+    }
+
+    #[test]
+    fn test_build_url_marketplaces() {
+        let expected = [
+            (Marketplace::Us, "https://www.audible.com/search?"),
+            (Marketplace::Uk, "https://www.audible.co.uk/search?"),
+            (Marketplace::De, "https://www.audible.de/search?"),
+            (Marketplace::Fr, "https://www.audible.fr/search?"),
+            (Marketplace::It, "https://www.audible.it/search?"),
+            (Marketplace::Es, "https://www.audible.es/search?"),
+            (Marketplace::Jp, "https://www.audible.co.jp/search?"),
+            (Marketplace::Au, "https://www.audible.com.au/search?"),
+            (Marketplace::Ca, "https://www.audible.ca/search?"),
+            (Marketplace::In, "https://www.audible.in/search?"),
+        ];
+
+        for (marketplace, prefix) in expected {
+            let url = QueryParams::new()
+                .marketplace(marketplace)
+                .keywords("dune")
+                .build_url()
+                .unwrap();
+
+            assert_eq!(
+                url.as_str(),
+                format!("{prefix}keywords=dune&sort=title-asc-rank&pageSize=50&page=1")
+            );
+            assert_eq!(Marketplace::from_url(&url), Some(marketplace));
+        }
+    }
+
+    #[test]
+    fn test_build_url_text_field_combinations() {
+        type Setter = fn(QueryParams, &str) -> QueryParams;
+        let fields: [(&str, &str, Setter); 5] = [
+            ("keywords", "dune", QueryParams::keywords),
+            ("title", "1793", QueryParams::title),
+            ("searchAuthor", "Frank Herbert", QueryParams::author),
+            ("searchNarrator", "Jordi Salas", QueryParams::narrator),
+            ("searchProvider", "Penguin", QueryParams::publisher),
+        ];
+
+        for mask in 0..(1u32 << fields.len()) {
+            let mut params = QueryParams::new();
+            let mut expected = String::from("https://www.audible.es/search?");
+
+            for (i, (key, value, set)) in fields.iter().enumerate() {
+                if mask & (1 << i) != 0 {
+                    params = set(params, value);
+                    expected.push_str(&format!("{}={}&", key, value.replace(' ', "+")));
+                }
+            }
+            expected.push_str("sort=title-asc-rank&pageSize=50&page=1");
+
+            assert_eq!(
+                params.build_url().unwrap().as_str(),
+                expected,
+                "mask {mask:05b}"
+            );
+        }
+    }
+}
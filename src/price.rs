@@ -0,0 +1,257 @@
+use regex::Regex;
+use serde::ser::{SerializeStruct, Serializer};
+use serde::Serialize;
+use std::fmt;
+use std::sync::LazyLock;
+
+use crate::Marketplace;
+
+// `18,99`, `1.234,56`, `12.99`, `1,500`, `1 234,56`
+static AMOUNT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\d(?:[\d.,\s\u{a0}\u{202f}]*\d)?").unwrap());
+
+/// Currency symbols shown by Audible, longest first so `CDN$` wins over `$`.
+const SYMBOLS: [(&str, &str); 10] = [
+    ("CDN$", "CAD"),
+    ("AU$", "AUD"),
+    ("A$", "AUD"),
+    ("C$", "CAD"),
+    ("US$", "USD"),
+    ("€", "EUR"),
+    ("£", "GBP"),
+    ("¥", "JPY"),
+    ("￥", "JPY"),
+    ("₹", "INR"),
+];
+
+/// An amount of money in a given currency.
+///
+/// The amount is kept as an integer number of minor units (e.g. cents) so prices compare and
+/// round-trip exactly; [`Price::amount`] formats it back as a decimal.
+///
+/// # Fields
+///
+/// * `amount_minor` - The amount, in minor units of the currency (e.g. `1899` for `18.99`).
+/// * `currency` - The ISO 4217 code of the currency, e.g. `"EUR"`.
+///
+/// # Examples
+///
+/// ```
+/// use audible_scraper::Price;
+///
+/// let price = Price::new(1899, "EUR");
+///
+/// assert_eq!(price.amount_minor(), 1899);
+/// assert_eq!(price.amount(), "18.99");
+/// assert_eq!(price.currency(), "EUR");
+/// assert_eq!(Price::new(1500, "JPY").amount(), "1500");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Price {
+    amount_minor: i64,
+    currency: String,
+}
+
+impl Price {
+    pub fn new(amount_minor: i64, currency: &str) -> Self {
+        Self {
+            amount_minor,
+            currency: currency.to_string(),
+        }
+    }
+
+    /// Parses a price as rendered by the marketplace, e.g. `18,99 €` or `$12.99`.
+    ///
+    /// The currency is taken from the symbol or ISO code in the text, falling back to the
+    /// currency of the marketplace (e.g. for `$`, which is used by several storefronts).
+    pub(crate) fn parse(text: &str, marketplace: Marketplace) -> Option<Self> {
+        let number = AMOUNT.find(text)?.as_str();
+        let decimal = marketplace.decimal_separator();
+
+        let currency = SYMBOLS
+            .iter()
+            .find(|(symbol, _)| text.contains(symbol))
+            .map(|(_, code)| *code)
+            .or_else(|| {
+                ["EUR", "USD", "GBP", "JPY", "AUD", "CAD", "INR"]
+                    .into_iter()
+                    .find(|code| text.contains(code))
+            })
+            .unwrap_or(marketplace.currency());
+
+        let (units, fraction) = number.split_once(decimal).unwrap_or((number, ""));
+        let digits = |text: &str| -> String { text.chars().filter(char::is_ascii_digit).collect() };
+        let (units, fraction) = (digits(units), digits(fraction));
+
+        // Pad the fraction to the minor units of the currency, only dropping trailing zeros
+        let minor_digits = minor_digits(currency);
+        let (fraction, rest) = fraction.split_at(fraction.len().min(minor_digits));
+        if rest.chars().any(|c| c != '0') {
+            return None;
+        }
+        let amount_minor = format!("{units}{fraction:0<minor_digits$}").parse().ok()?;
+
+        Some(Self::new(amount_minor, currency))
+    }
+
+    pub fn amount_minor(&self) -> i64 {
+        self.amount_minor
+    }
+
+    /// Returns the amount as a decimal number in units of the currency, e.g. `"18.99"`.
+    pub fn amount(&self) -> String {
+        let minor_digits = minor_digits(&self.currency);
+        if minor_digits == 0 {
+            return self.amount_minor.to_string();
+        }
+
+        let scale = 10i64.pow(minor_digits as u32);
+        let sign = if self.amount_minor < 0 { "-" } else { "" };
+        let amount = self.amount_minor.unsigned_abs();
+        format!(
+            "{sign}{}.{:0minor_digits$}",
+            amount / scale as u64,
+            amount % scale as u64
+        )
+    }
+
+    pub fn currency(&self) -> &str {
+        &self.currency
+    }
+}
+
+impl fmt::Display for Price {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.amount(), self.currency)
+    }
+}
+
+/// Serialized with the amount both as an exact decimal string and in minor units, never as a
+/// floating point number.
+impl Serialize for Price {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Price", 3)?;
+        state.serialize_field("amount", &self.amount())?;
+        state.serialize_field("amountMinor", &self.amount_minor)?;
+        state.serialize_field("currency", &self.currency)?;
+        state.end()
+    }
+}
+
+/// Number of digits of the minor units of `currency`: 0 for the yen, 2 for the others.
+fn minor_digits(currency: &str) -> usize {
+    match currency {
+        "JPY" => 0,
+        _ => 2,
+    }
+}
+
+/// The prices shown for an audio book in the search results.
+///
+/// # Fields
+///
+/// * `regular` - The regular price.
+/// * `sale` - The discounted price, while a sale is running.
+/// * `member` - The price for members of the marketplace.
+/// * `credits` - The number of credits needed to get the audio book.
+///
+/// Prices that Audible renders but hides (e.g. member prices for anonymous visitors) are
+/// left out.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Prices {
+    regular: Option<Price>,
+    sale: Option<Price>,
+    member: Option<Price>,
+    credits: Option<u32>,
+}
+
+impl Prices {
+    pub fn new(
+        regular: Option<Price>,
+        sale: Option<Price>,
+        member: Option<Price>,
+        credits: Option<u32>,
+    ) -> Self {
+        Self {
+            regular,
+            sale,
+            member,
+            credits,
+        }
+    }
+
+    pub fn regular(&self) -> Option<&Price> {
+        self.regular.as_ref()
+    }
+
+    pub fn sale(&self) -> Option<&Price> {
+        self.sale.as_ref()
+    }
+
+    pub fn member(&self) -> Option<&Price> {
+        self.member.as_ref()
+    }
+
+    pub fn credits(&self) -> Option<u32> {
+        self.credits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_parse_locales() {
+        let cases = [
+            (Marketplace::Es, "18,99 €", Price::new(1899, "EUR")),
+            (Marketplace::De, "1.234,56 €", Price::new(123456, "EUR")),
+            (
+                Marketplace::Fr,
+                "1\u{202f}234,56\u{a0}€",
+                Price::new(123456, "EUR"),
+            ),
+            (Marketplace::Us, "$12.99", Price::new(1299, "USD")),
+            (Marketplace::Us, "$1,234.99", Price::new(123499, "USD")),
+            (Marketplace::Us, "$9.5", Price::new(950, "USD")),
+            (Marketplace::Us, "$10", Price::new(1000, "USD")),
+            (Marketplace::Ca, "$24.95", Price::new(2495, "CAD")),
+            (Marketplace::Ca, "CDN$ 24.95", Price::new(2495, "CAD")),
+            (Marketplace::Au, "A$ 24.95", Price::new(2495, "AUD")),
+            (Marketplace::Uk, "£9.99", Price::new(999, "GBP")),
+            (Marketplace::Jp, "￥1,500", Price::new(1500, "JPY")),
+            (Marketplace::In, "₹ 499.00", Price::new(49900, "INR")),
+            (
+                Marketplace::Es,
+                "Precio habitual: 8,99",
+                Price::new(899, "EUR"),
+            ),
+        ];
+
+        for (marketplace, text, expected) in cases {
+            assert_eq!(Price::parse(text, marketplace), Some(expected), "{text}");
+        }
+
+        assert_eq!(Price::parse("Gratis", Marketplace::Es), None);
+    }
+
+    #[test]
+    fn test_price_amount() {
+        assert_eq!(Price::new(995, "EUR").amount(), "9.95");
+        assert_eq!(Price::new(5, "EUR").amount(), "0.05");
+        assert_eq!(Price::new(-1250, "USD").amount(), "-12.50");
+        assert_eq!(Price::new(1500, "JPY").amount(), "1500");
+        assert_eq!(Price::new(995, "EUR").to_string(), "9.95 EUR");
+
+        // Equal prices parse to equal values, whatever their rendering
+        assert_eq!(
+            Price::parse("9,95 €", Marketplace::Es),
+            Price::parse("EUR 9.950", Marketplace::Us)
+        );
+        assert_eq!(
+            serde_json::to_string(&Price::new(995, "EUR")).unwrap(),
+            r#"{"amount":"9.95","amountMinor":995,"currency":"EUR"}"#
+        );
+    }
+}
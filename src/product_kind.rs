@@ -0,0 +1,25 @@
+use serde::Serialize;
+use std::fmt;
+
+/// The kind of product listed in the search results.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ProductKind {
+    /// A regular audiobook.
+    #[default]
+    Audiobook,
+    /// A podcast show or episode.
+    Podcast,
+    /// An audiobook produced by Audible (Audible Original).
+    AudibleOriginal,
+}
+
+impl fmt::Display for ProductKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ProductKind::Audiobook => "audiobook",
+            ProductKind::Podcast => "podcast",
+            ProductKind::AudibleOriginal => "audibleOriginal",
+        })
+    }
+}
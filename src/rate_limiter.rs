@@ -0,0 +1,235 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// A token bucket limiting the rate of the requests sent to Audible.
+///
+/// The bucket holds up to `burst` tokens and refills at `requests_per_second`; each request
+/// takes a token, waiting for one if the bucket is empty, and is sent at least `min_delay`
+/// after the previous one. Clones share the same bucket, so a limiter given to several
+/// [`Builder`](crate::Builder)s throttles all their scrapers together.
+///
+/// # Fields
+///
+/// * `requests_per_second` - The sustained rate of the requests.
+/// * `burst` - The number of requests that can be sent back to back after a pause, 1 by default.
+/// * `min_delay` - The shortest time between two requests, none by default.
+///
+/// # Examples
+///
+/// ```
+/// use audible_scraper::{Builder, QueryParams, RateLimiter};
+/// use std::time::Duration;
+///
+/// let limiter = RateLimiter::new(2.0)
+///     .with_burst(4)
+///     .with_min_delay(Duration::from_millis(100));
+///
+/// let narrators = ["Jordi Salas", "Pep Anton Muñoz"];
+/// let scrapers = narrators.map(|narrator| {
+///     Builder::new(QueryParams::new().narrator(narrator))
+///         .rate_limiter(limiter.clone())
+///         .build()
+///         .unwrap()
+/// });
+/// ```
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+#[derive(Debug)]
+struct Bucket {
+    requests_per_second: f64,
+    burst: u32,
+    min_delay: Duration,
+    tokens: f64,
+    refilled_at: Instant,
+    last_request: Option<Instant>,
+}
+
+impl RateLimiter {
+    /// Creates a limiter allowing `requests_per_second` requests per second, one at a time.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `requests_per_second` is not a positive number.
+    pub fn new(requests_per_second: f64) -> Self {
+        assert!(
+            requests_per_second.is_finite() && requests_per_second > 0.0,
+            "The rate must be a positive number of requests per second"
+        );
+
+        Self {
+            bucket: Arc::new(Mutex::new(Bucket {
+                requests_per_second,
+                burst: 1,
+                min_delay: Duration::ZERO,
+                tokens: 1.0,
+                refilled_at: Instant::now(),
+                last_request: None,
+            })),
+        }
+    }
+
+    /// Sets the number of requests that can be sent back to back after a pause, at least one.
+    /// The bucket starts full.
+    pub fn with_burst(self, burst: u32) -> Self {
+        {
+            let mut bucket = self.bucket.lock().unwrap();
+            bucket.burst = burst.max(1);
+            bucket.tokens = bucket.burst as f64;
+        }
+        self
+    }
+
+    /// Sets the shortest time between two requests, whatever the tokens left in the bucket.
+    pub fn with_min_delay(self, min_delay: Duration) -> Self {
+        self.bucket.lock().unwrap().min_delay = min_delay;
+        self
+    }
+
+    pub fn requests_per_second(&self) -> f64 {
+        self.bucket.lock().unwrap().requests_per_second
+    }
+
+    pub fn burst(&self) -> u32 {
+        self.bucket.lock().unwrap().burst
+    }
+
+    pub fn min_delay(&self) -> Duration {
+        self.bucket.lock().unwrap().min_delay
+    }
+
+    /// Waits until a request may be sent, and takes its token.
+    pub async fn acquire(&self) {
+        loop {
+            let wait = self.bucket.lock().unwrap().try_take(Instant::now());
+            match wait {
+                Some(wait) => tokio::time::sleep(wait).await,
+                None => return,
+            }
+        }
+    }
+}
+
+impl Bucket {
+    /// Takes a token and returns `None` if a request may be sent at `now`, otherwise returns
+    /// how long to wait before trying again.
+    fn try_take(&mut self, now: Instant) -> Option<Duration> {
+        let elapsed = now.saturating_duration_since(self.refilled_at);
+        self.tokens =
+            (self.tokens + elapsed.as_secs_f64() * self.requests_per_second).min(self.burst as f64);
+        self.refilled_at = now;
+
+        let until_token = if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64((1.0 - self.tokens) / self.requests_per_second)
+        };
+        let until_min_delay = self.last_request.map_or(Duration::ZERO, |last_request| {
+            (last_request + self.min_delay).saturating_duration_since(now)
+        });
+
+        let wait = until_token.max(until_min_delay);
+        if wait.is_zero() {
+            self.tokens -= 1.0;
+            self.last_request = Some(now);
+            None
+        } else {
+            Some(wait)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the time taken to acquire `count` tokens.
+    async fn time_to_acquire(limiter: &RateLimiter, count: usize) -> Duration {
+        let start = Instant::now();
+        for _ in 0..count {
+            limiter.acquire().await;
+        }
+        start.elapsed()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate() {
+        let limiter = RateLimiter::new(20.0);
+        let elapsed = time_to_acquire(&limiter, 5).await;
+        assert_eq!(elapsed, Duration::from_millis(200));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_burst() {
+        let limiter = RateLimiter::new(5.0).with_burst(3);
+        assert_eq!(time_to_acquire(&limiter, 3).await, Duration::ZERO);
+        assert_eq!(
+            time_to_acquire(&limiter, 1).await,
+            Duration::from_millis(200)
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_min_delay() {
+        let limiter = RateLimiter::new(1000.0)
+            .with_burst(10)
+            .with_min_delay(Duration::from_millis(50));
+        let elapsed = time_to_acquire(&limiter, 4).await;
+        assert_eq!(elapsed, Duration::from_millis(150));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_clones_share_bucket() {
+        let limiter = RateLimiter::new(20.0);
+        let clone = limiter.clone();
+        let start = Instant::now();
+        tokio::join!(time_to_acquire(&limiter, 3), time_to_acquire(&clone, 3));
+        assert_eq!(start.elapsed(), Duration::from_millis(250));
+    }
+
+    #[test]
+    fn test_try_take() {
+        let limiter = RateLimiter::new(2.0).with_burst(2);
+        let mut bucket = limiter.bucket.lock().unwrap();
+        let now = bucket.refilled_at;
+        assert_eq!(bucket.try_take(now), None);
+        assert_eq!(bucket.try_take(now), None);
+        assert_eq!(bucket.try_take(now), Some(Duration::from_millis(500)));
+        assert_eq!(
+            bucket.try_take(now + Duration::from_millis(250)),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(bucket.try_take(now + Duration::from_millis(500)), None);
+
+        // The bucket refills up to the burst after a pause
+        let later = now + Duration::from_secs(10);
+        assert_eq!(bucket.try_take(later), None);
+        assert_eq!(bucket.try_take(later), None);
+        assert_eq!(bucket.try_take(later), Some(Duration::from_millis(500)));
+    }
+
+    #[test]
+    fn test_try_take_min_delay() {
+        let limiter = RateLimiter::new(1000.0)
+            .with_burst(10)
+            .with_min_delay(Duration::from_millis(50));
+        let mut bucket = limiter.bucket.lock().unwrap();
+        let now = bucket.refilled_at;
+        assert_eq!(bucket.try_take(now), None);
+        assert_eq!(bucket.try_take(now), Some(Duration::from_millis(50)));
+        assert_eq!(
+            bucket.try_take(now + Duration::from_millis(20)),
+            Some(Duration::from_millis(30))
+        );
+        assert_eq!(bucket.try_take(now + Duration::from_millis(50)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "positive number")]
+    fn test_invalid_rate() {
+        RateLimiter::new(0.0);
+    }
+}
@@ -0,0 +1,87 @@
+use regex::Regex;
+use serde::Serialize;
+use std::sync::LazyLock;
+
+// `4.5 out of 5 stars`, `4,5 von 5 Sternen`
+static AVERAGE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(\d+(?:[.,]\d+)?)").unwrap());
+// `50 calificaciones`, `1.234 calificaciones`, `1,234 ratings`
+static COUNT: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\d[\d.,\s\u{a0}]*").unwrap());
+
+/// The listener rating of an audio book.
+///
+/// # Fields
+///
+/// * `average` - The average overall rating, from 0 to 5 stars.
+/// * `count` - The number of ratings the average is based on.
+///
+/// # Examples
+///
+/// ```
+/// use audible_scraper::Rating;
+///
+/// let rating = Rating::new(4.5, 166);
+///
+/// assert_eq!(rating.average(), 4.5);
+/// assert_eq!(rating.count(), 166);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rating {
+    average: f32,
+    count: u32,
+}
+
+impl Rating {
+    pub fn new(average: f32, count: u32) -> Self {
+        Self { average, count }
+    }
+
+    /// Parses the texts of a `ratingsLabel`, e.g. `4.5 out of 5 stars` and `166 calificaciones`.
+    ///
+    /// Returns `None` when either text has no number or there are no ratings at all,
+    /// as for "sin calificaciones" or "Not rated yet".
+    pub(crate) fn parse(stars: &str, count: &str) -> Option<Self> {
+        let average = AVERAGE.captures(stars)?[1].replace(',', ".").parse().ok()?;
+        let count: u32 = COUNT
+            .find(count)?
+            .as_str()
+            .chars()
+            .filter(char::is_ascii_digit)
+            .collect::<String>()
+            .parse()
+            .ok()?;
+
+        (count > 0).then(|| Self::new(average, count))
+    }
+
+    pub fn average(&self) -> f32 {
+        self.average
+    }
+
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rating_parse_locales() {
+        assert_eq!(
+            Rating::parse("4.5 out of 5 stars", "1,234 ratings"),
+            Some(Rating::new(4.5, 1234))
+        );
+        assert_eq!(
+            Rating::parse("4,5 von 5 Sternen", "1.234 Bewertungen"),
+            Some(Rating::new(4.5, 1234))
+        );
+        assert_eq!(
+            Rating::parse("4.5 out of 5 stars", "12 345 évaluations"),
+            Some(Rating::new(4.5, 12345))
+        );
+        assert_eq!(Rating::parse("0 out of 5 stars", "Not rated yet"), None);
+        assert_eq!(Rating::parse("0 out of 5 stars", "0 ratings"), None);
+    }
+}
@@ -0,0 +1,304 @@
+use chrono::{DateTime, Utc};
+use reqwest::StatusCode;
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+use std::time::Duration;
+
+use crate::Error;
+
+/// Represents how the [`Scraper`](crate::Scraper) retries the requests that fail with a
+/// transient error, such as a `503 Service Unavailable` or a `429 Too Many Requests`.
+///
+/// The delay before each retry doubles from `base_delay` up to `max_delay`, and is shortened by
+/// a random fraction of up to `jitter` so concurrent requests do not retry in lockstep. When the
+/// server answers with a `Retry-After` header, its delay is used instead, unless it is longer
+/// than `max_delay`, in which case the request is not retried.
+///
+/// # Fields
+///
+/// * `max_attempts` - The number of times a request is sent, including the first one.
+/// * `base_delay` - The delay before the first retry.
+/// * `max_delay` - The longest delay between two attempts, including those asked by `Retry-After`.
+/// * `jitter` - The largest fraction of the delay removed at random, between 0 and 1.
+/// * `statuses` - The status codes worth retrying.
+/// * `transport_errors` - Whether connection failures and timeouts are retried.
+///
+/// # Examples
+///
+/// ```
+/// use audible_scraper::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new()
+///     .with_max_attempts(5)
+///     .with_base_delay(Duration::from_secs(1))
+///     .with_jitter(0.0);
+/// assert_eq!(policy.max_attempts(), 5);
+/// assert_eq!(policy.backoff(3), Duration::from_secs(4));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: f64,
+    statuses: Vec<StatusCode>,
+    transport_errors: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            jitter: 0.5,
+            statuses: vec![
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::INTERNAL_SERVER_ERROR,
+                StatusCode::BAD_GATEWAY,
+                StatusCode::SERVICE_UNAVAILABLE,
+                StatusCode::GATEWAY_TIMEOUT,
+            ],
+            transport_errors: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Creates the default policy: 3 attempts, 500 ms base delay, up to 30 s between attempts,
+    /// 50% jitter, retrying the 429, 500, 502, 503 and 504 statuses and transport errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a policy sending each request only once.
+    pub fn never() -> Self {
+        Self::default().with_max_attempts(1)
+    }
+
+    /// Sets the number of times a request is sent, including the first one; `0` is treated
+    /// as `1`.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Sets the largest fraction of the delay removed at random, clamped between 0 and 1.
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the status codes worth retrying, replacing the default ones.
+    pub fn with_statuses(mut self, statuses: impl IntoIterator<Item = StatusCode>) -> Self {
+        self.statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// Sets whether connection failures and timeouts are retried.
+    pub fn with_transport_errors(mut self, transport_errors: bool) -> Self {
+        self.transport_errors = transport_errors;
+        self
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub fn base_delay(&self) -> Duration {
+        self.base_delay
+    }
+
+    pub fn max_delay(&self) -> Duration {
+        self.max_delay
+    }
+
+    pub fn jitter(&self) -> f64 {
+        self.jitter
+    }
+
+    pub fn statuses(&self) -> &[StatusCode] {
+        &self.statuses
+    }
+
+    pub fn transport_errors(&self) -> bool {
+        self.transport_errors
+    }
+
+    /// Returns whether a request failing with `err` is worth sending again.
+    ///
+    /// Captchas and parse errors are never retried, since sending the same request again
+    /// right away would not change the answer.
+    pub fn is_retryable(&self, err: &Error) -> bool {
+        match err {
+            Error::Status { status, .. } => self.statuses.contains(status),
+            Error::Transport(err) => {
+                self.transport_errors && (err.is_connect() || err.is_timeout())
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the delay before the given retry, counted from 1, without jitter: `base_delay`
+    /// doubled for each previous retry, at most `max_delay`.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        self.base_delay
+            .checked_mul(factor)
+            .map_or(self.max_delay, |delay| delay.min(self.max_delay))
+    }
+
+    /// Returns the delay before sending again a request that failed with `err` on the given
+    /// attempt, counted from 1, or `None` if it should not be retried.
+    ///
+    /// A `Retry-After` delay longer than `max_delay` is not waited for: the request fails
+    /// right away with its status rather than blocking the search for that long.
+    pub(crate) fn delay(&self, attempt: u32, err: &Error) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.is_retryable(err) {
+            return None;
+        }
+
+        match err {
+            Error::Status {
+                retry_after: Some(retry_after),
+                ..
+            } => Some(*retry_after).filter(|delay| *delay <= self.max_delay),
+            _ => {
+                let backoff = self.backoff(attempt);
+                Some(backoff.mul_f64(1.0 - self.jitter * random_fraction()))
+            }
+        }
+    }
+}
+
+/// Returns a random number in `[0, 1)`, from the random keys of the standard library hashers.
+fn random_fraction() -> f64 {
+    (RandomState::new().hash_one(0u8) >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Parses the value of a `Retry-After` header, either a number of seconds or an HTTP date, into
+/// the delay to wait from `now`.
+pub(crate) fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use url::Url;
+
+    fn status(status: StatusCode, retry_after: Option<Duration>) -> Error {
+        Error::Status {
+            url: Url::parse("https://www.audible.es/search").unwrap(),
+            status,
+            retry_after,
+        }
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::new()
+            .with_base_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(500));
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(400));
+        assert_eq!(policy.backoff(4), Duration::from_millis(500));
+        assert_eq!(policy.backoff(100), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_delay() {
+        let policy = RetryPolicy::new()
+            .with_max_attempts(3)
+            .with_base_delay(Duration::from_millis(100))
+            .with_jitter(0.0);
+        let unavailable = status(StatusCode::SERVICE_UNAVAILABLE, None);
+        assert_eq!(
+            policy.delay(1, &unavailable),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            policy.delay(2, &unavailable),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(policy.delay(3, &unavailable), None);
+
+        let too_many = status(StatusCode::TOO_MANY_REQUESTS, Some(Duration::from_secs(7)));
+        assert_eq!(policy.delay(1, &too_many), Some(Duration::from_secs(7)));
+        let too_long = status(
+            StatusCode::TOO_MANY_REQUESTS,
+            Some(Duration::from_secs(86400)),
+        );
+        assert_eq!(policy.delay(1, &too_long), None);
+        let at_max = status(StatusCode::TOO_MANY_REQUESTS, Some(policy.max_delay));
+        assert_eq!(policy.delay(1, &at_max), Some(policy.max_delay));
+
+        assert_eq!(policy.delay(1, &status(StatusCode::NOT_FOUND, None)), None);
+        assert_eq!(
+            policy.delay(1, &Error::parse("items", "No items found")),
+            None
+        );
+        assert_eq!(RetryPolicy::never().delay(1, &unavailable), None);
+    }
+
+    #[test]
+    fn test_delay_jitter() {
+        let policy = RetryPolicy::new()
+            .with_base_delay(Duration::from_millis(100))
+            .with_jitter(0.5);
+        let unavailable = status(StatusCode::SERVICE_UNAVAILABLE, None);
+        for _ in 0..100 {
+            let delay = policy.delay(1, &unavailable).unwrap();
+            assert!(delay > Duration::from_millis(50) && delay <= Duration::from_millis(100));
+        }
+    }
+
+    #[test]
+    fn test_with_statuses() {
+        let policy = RetryPolicy::new().with_statuses([StatusCode::NOT_FOUND]);
+        assert!(policy.is_retryable(&status(StatusCode::NOT_FOUND, None)));
+        assert!(!policy.is_retryable(&status(StatusCode::SERVICE_UNAVAILABLE, None)));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = DateTime::parse_from_rfc3339("2024-11-06T08:49:37Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            parse_retry_after("120", now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 06 Nov 2024 08:50:07 GMT", now),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 06 Nov 2024 08:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+}
@@ -1,40 +1,669 @@
-use reqwest::Client;
-use std::error::Error;
+use chrono::Utc;
+use futures::future;
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use reqwest::{header, Client};
+use std::pin::pin;
 use url::Url;
 
-use crate::{PageResult, QueryParams};
+use crate::retry_policy::parse_retry_after;
+use crate::{
+    AudioBook, Error, PageKind, PageResult, QueryParams, RateLimiter, Result, RetryPolicy,
+};
+
+/// Number of pages fetched at the same time once the number of pages is known.
+pub(crate) const DEFAULT_CONCURRENCY: usize = 4;
 
 // Struct to hold search parameters for AudibleScraper
 pub struct Scraper {
     client: Client,
     params: QueryParams,
+    base_url: Option<Url>,
+    concurrency: usize,
+    retry_policy: RetryPolicy,
+    rate_limiter: Option<RateLimiter>,
 }
 
 impl Scraper {
     pub fn new(client: Client, params: QueryParams) -> Self {
-        Self { client, params }
+        Self {
+            client,
+            params,
+            base_url: None,
+            concurrency: DEFAULT_CONCURRENCY,
+            retry_policy: RetryPolicy::default(),
+            rate_limiter: None,
+        }
+    }
+
+    /// Sends the requests to `base_url` instead of the host of the marketplace, if any.
+    pub(crate) fn with_base_url(mut self, base_url: Option<Url>) -> Self {
+        self.base_url = base_url;
+        self
     }
 
-    pub async fn fetch(&self) -> Result<PageResult, Box<dyn Error>> {
-        Ok(self.fetch_page(self.params.clone().build_url()?).await?)
+    /// Sets the number of pages fetched at the same time, at least one.
+    pub(crate) fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets how the requests failing with a transient error are retried.
+    pub(crate) fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Waits for a token of `rate_limiter`, if any, before each request.
+    pub(crate) fn with_rate_limiter(mut self, rate_limiter: Option<RateLimiter>) -> Self {
+        self.rate_limiter = rate_limiter;
+        self
+    }
+
+    /// Fetches the first page of the search.
+    ///
+    /// The typed filters whose refinement id is not known on the marketplace, nor found by an
+    /// earlier search, are first resolved from the facets of the search without them, which
+    /// takes one more request.
+    pub async fn fetch(&self) -> Result<PageResult> {
+        let url = match self.params.build_url() {
+            Err(Error::UnsupportedFilter { .. }) => {
+                let params = match self.params.clone().resolve_filters(&[]) {
+                    Ok(params) => params,
+                    Err(_) => {
+                        let unfiltered =
+                            self.fetch_page(self.params.without_filters().build_url()?);
+                        let facets = unfiltered.await?.facets();
+                        self.params.clone().resolve_filters(&facets)?
+                    }
+                };
+                params.build_url()?
+            }
+            url => url?,
+        };
+        self.fetch_page(url).await
     }
 
     // Fetch all pages until the last one
-    pub async fn fetch_all(&self) -> Result<Vec<PageResult>, Box<dyn Error>> {
-        let mut result = self.fetch().await?;
-        let mut results = vec![result.clone()];
+    pub async fn fetch_all(&self) -> Result<Vec<PageResult>> {
+        self.fetch_all_with_progress(|_| {}).await
+    }
+
+    /// Fetches all pages until the last one, calling `on_page` with each page once fetched.
+    ///
+    /// See [`Scraper::stream_pages`] for how the pages are found.
+    pub async fn fetch_all_with_progress(
+        &self,
+        mut on_page: impl FnMut(&PageResult),
+    ) -> Result<Vec<PageResult>> {
+        let mut pages = pin!(self.stream_pages());
+        let mut results = Vec::new();
 
-        // Continue fetching while there is a next page
-        while let Some(next_page_url) = result.next_page_url() {
-            result = self.fetch_page(next_page_url).await?; // Fetch the next page by URL
-            results.push(result.clone());
+        while let Some(result) = pages.next().await {
+            let result = result?;
+            on_page(&result);
+            results.push(result);
         }
 
         Ok(results)
     }
 
-    async fn fetch_page(&self, url: Url) -> Result<PageResult, Box<dyn Error>> {
-        let res = self.client.get(url.clone()).send().await?;
-        Ok(PageResult::new(url, res.text().await?))
+    /// Returns a stream of the pages of the search in page order, fetched as the stream is
+    /// polled, so callers can stop early without fetching the remaining pages.
+    ///
+    /// When the first page advertises the number of pages, the remaining pages are fetched
+    /// concurrently, up to the concurrency set with [`Builder::concurrency`](crate::Builder::concurrency);
+    /// otherwise the "next" links are followed one page after the other. The stream ends after
+    /// the first request that fails, yielding its error.
+    pub fn stream_pages(&self) -> impl Stream<Item = Result<PageResult>> + '_ {
+        let pages = stream::once(self.fetch())
+            .flat_map(move |first| {
+                let remaining = match &first {
+                    Ok(page) => self.remaining_pages(page),
+                    Err(_) => stream::empty().boxed(),
+                };
+                stream::once(future::ready(first)).chain(remaining)
+            })
+            .boxed();
+
+        // Drop the remaining pages after an error rather than polling them
+        stream::unfold(Some(pages), |pages| async move {
+            let mut pages = pages?;
+            let result = pages.next().await?;
+            let pages = result.is_ok().then_some(pages);
+            Some((result, pages))
+        })
+    }
+
+    /// Returns a stream of the pages following `first`.
+    fn remaining_pages(&self, first: &PageResult) -> BoxStream<'_, Result<PageResult>> {
+        match first.page_urls() {
+            Some(urls) => stream::iter(urls.into_iter().skip(first.page() as usize))
+                .map(move |url| self.fetch_page(url))
+                .buffered(self.concurrency)
+                .boxed(),
+            // Continue fetching while there is a next page
+            None => stream::unfold(first.next_page_url(), move |next| async move {
+                match self.fetch_page(next?).await {
+                    Ok(page) => {
+                        let next = page.next_page_url();
+                        Some((Ok(page), next))
+                    }
+                    Err(err) => Some((Err(err), None)),
+                }
+            })
+            .boxed(),
+        }
+    }
+
+    /// Returns a stream of the audiobooks of the search, page after page as they arrive.
+    ///
+    /// Each problem found while extracting an item is yielded as an [`Error::Parse`] after the
+    /// audiobooks of its page, see [`PageResult::collect_lenient`]; filter them out with
+    /// `filter_map(|book| async { book.ok() })` to only keep the audiobooks. The stream ends
+    /// after the first request that fails, yielding its error.
+    pub fn stream(&self) -> impl Stream<Item = Result<AudioBook>> + '_ {
+        self.stream_pages().flat_map(|page| {
+            let items: Vec<Result<AudioBook>> = match page.and_then(|page| page.collect_lenient()) {
+                Ok((audiobooks, diagnostics)) => audiobooks
+                    .into_iter()
+                    .map(Ok)
+                    .chain(
+                        diagnostics
+                            .into_iter()
+                            .map(|diagnostic| Err(diagnostic.into())),
+                    )
+                    .collect(),
+                Err(err) => vec![Err(err)],
+            };
+            stream::iter(items)
+        })
+    }
+
+    /// Fetches a page, retrying the transient failures as set by the retry policy.
+    async fn fetch_page(&self, url: Url) -> Result<PageResult> {
+        let mut attempt = 1;
+        loop {
+            let err = match self.try_fetch_page(url.clone()).await {
+                Ok(page) => return Ok(page),
+                Err(err) => err,
+            };
+            match self.retry_policy.delay(attempt, &err) {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return Err(err),
+            }
+            attempt += 1;
+        }
+    }
+
+    async fn try_fetch_page(&self, url: Url) -> Result<PageResult> {
+        if let Some(rate_limiter) = &self.rate_limiter {
+            rate_limiter.acquire().await;
+        }
+
+        let res = self.client.get(self.request_url(&url)?).send().await?;
+        let status = res.status();
+        if !status.is_success() {
+            let retry_after = res
+                .headers()
+                .get(header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| parse_retry_after(value, Utc::now()));
+            return Err(Error::Status {
+                url,
+                status,
+                retry_after,
+            });
+        }
+
+        let body = res.text().await?;
+        let result = PageResult::with_marketplace(url, body, self.params.get_marketplace());
+        let url = result.url().clone();
+        match result.kind() {
+            PageKind::Results | PageKind::NoResults => {}
+            PageKind::Blocked => return Err(Error::Blocked { url }),
+            PageKind::UnexpectedLayout => return Err(Error::UnexpectedLayout { url }),
+        }
+
+        // Audible silently replaces page sizes it does not offer, breaking the page arithmetic
+        let requested = self.params.get_page_size();
+        if let Some(served) = result.page_size().filter(|served| *served != requested) {
+            return Err(Error::PageSizeMismatch {
+                url,
+                requested,
+                served,
+            });
+        }
+
+        Ok(result)
+    }
+
+    /// Returns the URL to request for a page of the marketplace, on the base URL if any.
+    fn request_url(&self, url: &Url) -> Result<Url> {
+        match &self.base_url {
+            Some(base_url) => {
+                let mut request_url = base_url.join(url.path())?;
+                request_url.set_query(url.query());
+                Ok(request_url)
+            }
+            None => Ok(url.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::page_result::tests::{item, label, CAPTCHA_PAGE, NO_RESULTS_PAGE, SAMPLE_BUTTON};
+    use crate::test_server::{Response, TestServer};
+    use crate::{Builder, Language, Length, Marketplace};
+    use futures::TryStreamExt;
+    use std::fs;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::Notify;
+    use tokio::time::Instant;
+
+    /// Serves the first and last result pages fixtures for pages 1 and 3, and the first one
+    /// again for page 2.
+    async fn fixture_server() -> TestServer {
+        let first = fs::read_to_string("test_data/result_first_page.html").unwrap();
+        let last = fs::read_to_string("test_data/result_last_page.html").unwrap();
+
+        TestServer::start(move |url| {
+            let page = url
+                .query_pairs()
+                .find(|(key, _)| key == "page")
+                .map(|(_, value)| value.into_owned());
+            match page.as_deref() {
+                Some("1") | Some("2") => Response::ok(first.clone()),
+                Some("3") => Response::ok(last.clone()),
+                _ => Response::status(404),
+            }
+        })
+        .await
+    }
+
+    /// Serves the pages of a search of 3 small pages, see [`small_page`].
+    async fn pages_server() -> TestServer {
+        TestServer::start(|url| Response::ok(small_page(page_param(url), true))).await
+    }
+
+    fn scraper(server: &TestServer, concurrency: usize) -> Scraper {
+        Builder::new(QueryParams::new().narrator("Jordi Salas"))
+            .base_url(server.url().clone())
+            .concurrency(concurrency)
+            .retry_policy(RetryPolicy::never())
+            .build()
+            .unwrap()
+    }
+
+    fn requested_pages(server: &TestServer) -> Vec<String> {
+        server
+            .requests()
+            .iter()
+            .filter_map(|url| url.query_pairs().find(|(key, _)| key == "page"))
+            .map(|(_, value)| value.into_owned())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_scraper_stream_pages() {
+        let server = pages_server().await;
+        let scraper = scraper(&server, 1);
+
+        let pages: Vec<PageResult> = scraper.stream_pages().try_collect().await.unwrap();
+        let numbers: Vec<u32> = pages.iter().map(PageResult::page).collect();
+        assert_eq!(numbers, [1, 2, 3]);
+        assert_eq!(requested_pages(&server), ["1", "2", "3"]);
+        assert_eq!(
+            pages[0].url().as_str(),
+            "https://www.audible.es/search?searchNarrator=Jordi+Salas&sort=title-asc-rank&pageSize=50&page=1"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_scraper_stream_stops_early() {
+        let server = pages_server().await;
+        let scraper = scraper(&server, 1);
+
+        let audiobooks: Vec<AudioBook> = scraper.stream().take(2).try_collect().await.unwrap();
+        assert_eq!(audiobooks.len(), 2);
+        assert_eq!(audiobooks[0].title(), "Item 1.1");
+        assert_eq!(requested_pages(&server), ["1"]);
+    }
+
+    #[tokio::test]
+    async fn test_scraper_stream_error_ends_stream() {
+        let server = TestServer::start(|url| {
+            if page_param(url) == 1 {
+                Response::ok(small_page(1, true))
+            } else {
+                Response::status(503)
+            }
+        })
+        .await;
+        let scraper = scraper(&server, 1);
+
+        let results: Vec<Result<PageResult>> = scraper.stream_pages().collect().await;
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        assert!(matches!(
+            &results[1],
+            Err(Error::Status { status, .. }) if status.as_u16() == 503
+        ));
+        assert_eq!(requested_pages(&server), ["1", "2"]);
+
+        let audiobooks: Vec<Result<AudioBook>> = scraper.stream().collect().await;
+        assert_eq!(audiobooks.iter().filter(|book| book.is_ok()).count(), 2);
+        assert!(audiobooks.last().unwrap().is_err());
+    }
+
+    /// Returns the `page` parameter of a request.
+    fn page_param(url: &Url) -> u32 {
+        url.query_pairs()
+            .find(|(key, _)| key == "page")
+            .and_then(|(_, value)| value.parse().ok())
+            .unwrap_or(1)
+    }
+
+    /// Returns page `page` of a search of 3 pages of 2 audiobooks, with its results summary
+    /// when `summary` is set, otherwise with only its "next" link to find the following page.
+    fn small_page(page: u32, summary: bool) -> String {
+        let items: String = (1..=2)
+            .map(|n| {
+                let details = format!(
+                    "{SAMPLE_BUTTON}<ul>{}{}</ul>",
+                    label("narratorLabel", "<a>Jordi Salas</a>"),
+                    label("languageLabel", "Español")
+                );
+                item(
+                    &format!("B0000000{page}{n}"),
+                    &format!("Item {page}.{n}"),
+                    &details,
+                )
+            })
+            .collect();
+        let summary = if summary {
+            format!(
+                r#"<span class="resultsSummarySubheading">{} - {} de 6 resultados</span>
+                <span class="pageNumberElement">{page}</span>"#,
+                page * 2 - 1,
+                page * 2
+            )
+        } else {
+            String::new()
+        };
+        let next = if page < 3 {
+            format!(
+                r#"<span class="nextButton"><a href="/search?searchNarrator=Jordi+Salas&sort=title-asc-rank&pageSize=50&page={}">Next</a></span>"#,
+                page + 1
+            )
+        } else {
+            String::new()
+        };
+        format!("{summary}<ul>{items}</ul>{next}")
+    }
+
+    #[tokio::test]
+    async fn test_scraper_stream_pages_concurrently() {
+        // The second page is only served once the third one is requested, so both must be
+        // fetched at the same time; the pages must still be returned in order
+        let third_requested = Arc::new(Notify::new());
+        let notify = third_requested.clone();
+        let counted = TestServer::start(move |url| match page_param(url) {
+            2 => Response::ok(small_page(2, true)).wait(third_requested.clone()),
+            3 => {
+                notify.notify_one();
+                Response::ok(small_page(3, true))
+            }
+            page => Response::ok(small_page(page, true)),
+        })
+        .await;
+        // Without a results summary, the pages are found by following the "next" links
+        let linked =
+            TestServer::start(|url| Response::ok(small_page(page_param(url), false))).await;
+
+        let counted_scraper = scraper(&counted, 4);
+        let concurrent = counted_scraper.stream_pages().try_collect();
+        let concurrent: Vec<PageResult> = tokio::time::timeout(Duration::from_secs(10), concurrent)
+            .await
+            .expect("the second and third pages are fetched concurrently")
+            .unwrap();
+        assert_eq!(counted.max_in_flight(), 2);
+
+        let sequential: Vec<PageResult> = scraper(&linked, 4)
+            .stream_pages()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(requested_pages(&linked), ["1", "2", "3"]);
+        assert_eq!(linked.max_in_flight(), 1);
+
+        let urls = |pages: &[PageResult]| -> Vec<Url> {
+            pages.iter().map(|page| page.url().clone()).collect()
+        };
+        assert_eq!(urls(&concurrent), urls(&sequential));
+        assert!(concurrent[1].url().as_str().ends_with("page=2"));
+
+        // Same output as following the "next" links, field by field
+        let audiobooks = |pages: &[PageResult]| -> String {
+            let audiobooks: Vec<AudioBook> = pages
+                .iter()
+                .flat_map(|page| page.collect().unwrap())
+                .collect();
+            serde_json::to_string(&audiobooks).unwrap()
+        };
+        assert_eq!(audiobooks(&concurrent), audiobooks(&sequential));
+        let count: usize = concurrent
+            .iter()
+            .map(|page| page.collect().unwrap().len())
+            .sum();
+        assert_eq!(count, 6);
+    }
+
+    /// Answers the first `failures` requests with `failure`, then the first page.
+    async fn flaky_server(
+        failures: usize,
+        failure: impl Fn() -> Response + Send + Sync + 'static,
+    ) -> TestServer {
+        let count = AtomicUsize::new(0);
+        TestServer::start(move |_| {
+            if count.fetch_add(1, Ordering::SeqCst) < failures {
+                failure()
+            } else {
+                Response::ok(small_page(1, true))
+            }
+        })
+        .await
+    }
+
+    fn retrying_scraper(server: &TestServer, retry_policy: RetryPolicy) -> Scraper {
+        Builder::new(QueryParams::new().narrator("Jordi Salas"))
+            .base_url(server.url().clone())
+            .retry_policy(retry_policy)
+            .build()
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_scraper_retries_transient_statuses() {
+        let server = flaky_server(2, || Response::status(503)).await;
+        let policy = RetryPolicy::new().with_base_delay(Duration::from_millis(10));
+
+        let page = retrying_scraper(&server, policy).fetch().await.unwrap();
+        assert_eq!(page.page(), 1);
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_scraper_gives_up_after_max_attempts() {
+        let server = flaky_server(usize::MAX, || Response::status(503)).await;
+        let policy = RetryPolicy::new()
+            .with_max_attempts(4)
+            .with_base_delay(Duration::from_millis(10));
+
+        let err = retrying_scraper(&server, policy).fetch().await.unwrap_err();
+        assert!(matches!(err, Error::Status { status, .. } if status.as_u16() == 503));
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_scraper_does_not_retry_other_statuses() {
+        let server = flaky_server(1, || Response::status(404)).await;
+
+        let err = retrying_scraper(&server, RetryPolicy::new())
+            .fetch()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::Status { status, .. } if status.as_u16() == 404));
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scraper_honours_retry_after() {
+        let server = flaky_server(1, || Response::status(429).header("Retry-After", "1")).await;
+        let policy = RetryPolicy::new().with_base_delay(Duration::from_millis(10));
+
+        // The clock is paused, so the delay is only waited for on the test clock
+        let start = Instant::now();
+        retrying_scraper(&server, policy).fetch().await.unwrap();
+        assert!(start.elapsed() >= Duration::from_secs(1));
+        assert_eq!(server.requests().len(), 2);
+
+        let server = flaky_server(1, || Response::status(429).header("Retry-After", "7")).await;
+        let err = retrying_scraper(&server, RetryPolicy::never())
+            .fetch()
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Status { retry_after: Some(delay), .. } if delay == Duration::from_secs(7)
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scraper_shares_rate_limiter() {
+        let server = pages_server().await;
+        let limiter = RateLimiter::new(10.0);
+        let scraper = |narrator: &str| {
+            Builder::new(QueryParams::new().narrator(narrator))
+                .base_url(server.url().clone())
+                .rate_limiter(limiter.clone())
+                .build()
+                .unwrap()
+        };
+        let (first, second) = (scraper("Jordi Salas"), scraper("Pep Anton Muñoz"));
+
+        // 6 requests at 10 per second, the first one without waiting
+        let start = Instant::now();
+        let (first, second) = tokio::join!(first.fetch_all(), second.fetch_all());
+        assert_eq!(first.unwrap().len(), 3);
+        assert_eq!(second.unwrap().len(), 3);
+        assert!(start.elapsed() >= Duration::from_millis(500));
+        assert_eq!(server.requests().len(), 6);
+    }
+
+    #[tokio::test]
+    async fn test_scraper_page_kinds() {
+        let server = TestServer::start(|url| match url.query_pairs().next() {
+            Some((_, keywords)) if keywords == "captcha" => Response::ok(CAPTCHA_PAGE),
+            Some((_, keywords)) if keywords == "xyzzy" => Response::ok(NO_RESULTS_PAGE),
+            _ => Response::ok("<html><body><h1>Audible</h1></body></html>"),
+        })
+        .await;
+        let fetch = |keywords: &str| {
+            Builder::new(QueryParams::new().keywords(keywords))
+                .base_url(server.url().clone())
+                .build()
+                .unwrap()
+        };
+
+        let err = fetch("captcha").fetch().await.unwrap_err();
+        assert!(matches!(err, Error::Blocked { .. }));
+        let pages = fetch("xyzzy").fetch_all().await.unwrap();
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].kind(), PageKind::NoResults);
+        assert!(pages[0].collect().unwrap().is_empty());
+        let err = fetch("redesign").fetch_all().await.unwrap_err();
+        assert!(matches!(err, Error::UnexpectedLayout { .. }));
+
+        // None of them is worth retrying
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_scraper_resolves_filters_from_facets() {
+        let server = fixture_server().await;
+        let params = QueryParams::new()
+            .marketplace(Marketplace::Us)
+            .narrator("Jordi Salas")
+            .language(Language::Spanish)
+            .length(Length::UnderOneHour);
+        let scraper = Builder::new(params)
+            .base_url(server.url().clone())
+            .retry_policy(RetryPolicy::never())
+            .build()
+            .unwrap();
+
+        scraper.fetch().await.unwrap();
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert!(!requests[0].as_str().contains("browse-bin"));
+        assert!(requests[1]
+            .as_str()
+            .contains("feature_six_browse-bin=18385686031&feature_seven_browse-bin=18385624031"));
+
+        // The filters found are remembered for the later searches on the marketplace
+        let params = QueryParams::new()
+            .marketplace(Marketplace::Us)
+            .narrator("Scott Brick")
+            .length(Length::UnderOneHour);
+        Builder::new(params)
+            .base_url(server.url().clone())
+            .build()
+            .unwrap()
+            .fetch()
+            .await
+            .unwrap();
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[2]
+            .as_str()
+            .contains("feature_seven_browse-bin=18385624031"));
+
+        // A filter absent from the facets cannot be resolved
+        let params = QueryParams::new()
+            .marketplace(Marketplace::Us)
+            .narrator("Jordi Salas")
+            .language(Language::Japanese);
+        let err = Builder::new(params)
+            .base_url(server.url().clone())
+            .build()
+            .unwrap()
+            .fetch()
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UnsupportedFilter {
+                filter: "language",
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_scraper_fetch_all() {
+        let server = pages_server().await;
+        let scraper = scraper(&server, 1);
+
+        let mut progress = Vec::new();
+        let pages = scraper
+            .fetch_all_with_progress(|page| progress.push(page.total_pages()))
+            .await
+            .unwrap();
+        assert_eq!(pages.len(), 3);
+        assert_eq!(progress, [Some(3), Some(3), Some(3)]);
     }
 }
@@ -0,0 +1,69 @@
+use serde::Serialize;
+use url::Url;
+
+/// A series an audio book belongs to.
+///
+/// # Fields
+///
+/// * `name` - The title of the series.
+/// * `url` - The Audible series page.
+/// * `asin` - The ASIN of the series taken from the series page URL, if available.
+/// * `position` - The position of the book in the series as shown by Audible, e.g. "3" or "2.5".
+///
+/// # Examples
+///
+/// ```
+/// use url::Url;
+/// use audible_scraper::Series;
+///
+/// let url = Url::parse("https://www.audible.es/series/Trilogia-de-Estocolmo-Audiolibro/B09SH6FSW6").unwrap();
+/// let series = Series::new(String::from("Trilogía de Estocolmo"), Some(url), Some(String::from("1")));
+///
+/// assert_eq!(series.name(), "Trilogía de Estocolmo");
+/// assert_eq!(series.asin(), Some("B09SH6FSW6"));
+/// assert_eq!(series.position(), Some("1"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Series {
+    name: String,
+    url: Option<Url>,
+    asin: Option<String>,
+    position: Option<String>,
+}
+
+impl Series {
+    /// Creates a new series, taking the ASIN from the URL when it points to a series page.
+    pub fn new(name: String, url: Option<Url>, position: Option<String>) -> Self {
+        let asin = url.as_ref().and_then(|url| {
+            let mut segments = url.path_segments()?;
+            match (segments.next(), segments.next_back()) {
+                (Some("series"), Some(asin)) if !asin.is_empty() => Some(asin.to_string()),
+                _ => None,
+            }
+        });
+
+        Self {
+            name,
+            url,
+            asin,
+            position,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn url(&self) -> Option<&Url> {
+        self.url.as_ref()
+    }
+
+    pub fn asin(&self) -> Option<&str> {
+        self.asin.as_deref()
+    }
+
+    pub fn position(&self) -> Option<&str> {
+        self.position.as_deref()
+    }
+}
@@ -0,0 +1,150 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// The order of the results of a search, as offered by the sort dropdown of Audible.
+///
+/// Each variant is written in URLs, command line arguments and files with its wire value,
+/// e.g. `"title-asc-rank"`.
+///
+/// # Examples
+///
+/// ```
+/// use audible_scraper::SortOrder;
+///
+/// let sort: SortOrder = "pubdate-desc-rank".parse().unwrap();
+///
+/// assert_eq!(sort, SortOrder::ReleaseDateDesc);
+/// assert_eq!(sort.to_string(), "pubdate-desc-rank");
+/// assert_eq!(SortOrder::default(), SortOrder::TitleAsc);
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum SortOrder {
+    /// Most popular first (`popularity-rank`).
+    #[serde(rename = "popularity-rank")]
+    #[cfg_attr(feature = "cli", value(name = "popularity-rank"))]
+    Popularity,
+    /// Best rated first (`review-rank`).
+    #[serde(rename = "review-rank")]
+    #[cfg_attr(feature = "cli", value(name = "review-rank"))]
+    Rating,
+    /// Cheapest first (`price-asc-rank`).
+    #[serde(rename = "price-asc-rank")]
+    #[cfg_attr(feature = "cli", value(name = "price-asc-rank"))]
+    PriceAsc,
+    /// Most expensive first (`price-desc-rank`).
+    #[serde(rename = "price-desc-rank")]
+    #[cfg_attr(feature = "cli", value(name = "price-desc-rank"))]
+    PriceDesc,
+    /// Oldest first (`pubdate-asc-rank`).
+    #[serde(rename = "pubdate-asc-rank")]
+    #[cfg_attr(feature = "cli", value(name = "pubdate-asc-rank"))]
+    ReleaseDateAsc,
+    /// Newest first (`pubdate-desc-rank`).
+    #[serde(rename = "pubdate-desc-rank")]
+    #[cfg_attr(feature = "cli", value(name = "pubdate-desc-rank"))]
+    ReleaseDateDesc,
+    /// Shortest first (`runtime-asc-rank`).
+    #[serde(rename = "runtime-asc-rank")]
+    #[cfg_attr(feature = "cli", value(name = "runtime-asc-rank"))]
+    RuntimeAsc,
+    /// Longest first (`runtime-desc-rank`).
+    #[serde(rename = "runtime-desc-rank")]
+    #[cfg_attr(feature = "cli", value(name = "runtime-desc-rank"))]
+    RuntimeDesc,
+    /// Title from A to Z (`title-asc-rank`).
+    #[default]
+    #[serde(rename = "title-asc-rank")]
+    #[cfg_attr(feature = "cli", value(name = "title-asc-rank"))]
+    TitleAsc,
+    /// Title from Z to A (`title-desc-rank`).
+    #[serde(rename = "title-desc-rank")]
+    #[cfg_attr(feature = "cli", value(name = "title-desc-rank"))]
+    TitleDesc,
+}
+
+impl SortOrder {
+    pub const ALL: [SortOrder; 10] = [
+        SortOrder::Popularity,
+        SortOrder::Rating,
+        SortOrder::PriceAsc,
+        SortOrder::PriceDesc,
+        SortOrder::ReleaseDateAsc,
+        SortOrder::ReleaseDateDesc,
+        SortOrder::RuntimeAsc,
+        SortOrder::RuntimeDesc,
+        SortOrder::TitleAsc,
+        SortOrder::TitleDesc,
+    ];
+
+    /// Value of the `sort` query parameter, e.g. `"title-asc-rank"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SortOrder::Popularity => "popularity-rank",
+            SortOrder::Rating => "review-rank",
+            SortOrder::PriceAsc => "price-asc-rank",
+            SortOrder::PriceDesc => "price-desc-rank",
+            SortOrder::ReleaseDateAsc => "pubdate-asc-rank",
+            SortOrder::ReleaseDateDesc => "pubdate-desc-rank",
+            SortOrder::RuntimeAsc => "runtime-asc-rank",
+            SortOrder::RuntimeDesc => "runtime-desc-rank",
+            SortOrder::TitleAsc => "title-asc-rank",
+            SortOrder::TitleDesc => "title-desc-rank",
+        }
+    }
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SortOrder {
+    type Err = Error;
+
+    /// Parses the wire value of a sort order, e.g. `"title-asc-rank"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an [`Error::Parse`] if `s` is not the value of a sort order.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Self::ALL
+            .into_iter()
+            .find(|sort| sort.as_str() == s)
+            .ok_or_else(|| Error::parse("sort order", format!("unknown value {s:?}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_order_round_trip() {
+        for sort in SortOrder::ALL {
+            assert_eq!(sort.to_string().parse::<SortOrder>().unwrap(), sort);
+            assert_eq!(
+                serde_json::to_string(&sort).unwrap(),
+                format!("\"{}\"", sort)
+            );
+            #[cfg(feature = "cli")]
+            assert_eq!(
+                <SortOrder as clap::ValueEnum>::from_str(sort.as_str(), false),
+                Ok(sort)
+            );
+        }
+
+        let err = "relevance".parse::<SortOrder>().unwrap_err();
+        assert!(matches!(err, Error::Parse { .. }));
+        assert_eq!(
+            err.to_string(),
+            "Cannot parse sort order: unknown value \"relevance\""
+        );
+    }
+}
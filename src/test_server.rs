@@ -0,0 +1,130 @@
+//! A minimal HTTP server to test the scraper against canned responses.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+use tokio::sync::Notify;
+use url::Url;
+
+/// A canned HTTP response.
+pub(crate) struct Response {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+    wait: Option<Arc<Notify>>,
+}
+
+impl Response {
+    pub(crate) fn ok(body: impl Into<String>) -> Self {
+        Self::status(200).body(body)
+    }
+
+    pub(crate) fn status(status: u16) -> Self {
+        Self {
+            status,
+            headers: Vec::new(),
+            body: String::new(),
+            wait: None,
+        }
+    }
+
+    pub(crate) fn body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    pub(crate) fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Waits for `notify` to be notified before sending the response.
+    pub(crate) fn wait(mut self, notify: Arc<Notify>) -> Self {
+        self.wait = Some(notify);
+        self
+    }
+}
+
+/// Serves the responses of `handler`, called with the URL of each request, on a local port.
+pub(crate) struct TestServer {
+    url: Url,
+    requests: Arc<Mutex<Vec<Url>>>,
+    max_in_flight: Arc<AtomicUsize>,
+}
+
+impl TestServer {
+    pub(crate) async fn start(handler: impl Fn(&Url) -> Response + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}/", listener.local_addr().unwrap())).unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let handler = Arc::new(handler);
+
+        let (base, log, max) = (url.clone(), requests.clone(), max_in_flight.clone());
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let (base, log, handler) = (base.clone(), log.clone(), handler.clone());
+                let (in_flight, max) = (in_flight.clone(), max.clone());
+                tokio::spawn(async move {
+                    let mut request = Vec::new();
+                    let mut buf = [0; 4096];
+                    while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                        match stream.read(&mut buf).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => request.extend_from_slice(&buf[..n]),
+                        }
+                    }
+
+                    let request = String::from_utf8_lossy(&request);
+                    let target = request.split_whitespace().nth(1).unwrap_or("/");
+                    let url = base.join(target).unwrap();
+                    log.lock().unwrap().push(url.clone());
+                    let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max.fetch_max(current, Ordering::SeqCst);
+
+                    let response = handler(&url);
+                    if let Some(notify) = &response.wait {
+                        notify.notified().await;
+                    }
+                    let mut head = format!(
+                        "HTTP/1.1 {} Test\r\nContent-Length: {}\r\nConnection: close\r\n",
+                        response.status,
+                        response.body.len()
+                    );
+                    for (name, value) in &response.headers {
+                        head.push_str(&format!("{name}: {value}\r\n"));
+                    }
+                    head.push_str("\r\n");
+
+                    let _ = stream.write_all(head.as_bytes()).await;
+                    let _ = stream.write_all(response.body.as_bytes()).await;
+                    let _ = stream.shutdown().await;
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+        });
+
+        Self {
+            url,
+            requests,
+            max_in_flight,
+        }
+    }
+
+    /// The base URL of the server, e.g. `http://127.0.0.1:34567/`.
+    pub(crate) fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// The URLs requested so far, in the order they were received.
+    pub(crate) fn requests(&self) -> Vec<Url> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// The largest number of requests handled at the same time so far.
+    pub(crate) fn max_in_flight(&self) -> usize {
+        self.max_in_flight.load(Ordering::SeqCst)
+    }
+}
@@ -1,37 +1,479 @@
-use chrono::NaiveDate;
+use chrono::{Datelike, NaiveDate, Utc};
 use regex::Regex;
+use std::fmt;
+use std::sync::LazyLock;
+use std::time::Duration;
 
-/// Extracts a date in the format dd-mm-yy from a given text string.
+use crate::{DateOrder, Marketplace};
+
+// `2020年7月30日`
+static KANJI_DATE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d{4})\s*年\s*(\d{1,2})\s*月\s*(\d{1,2})\s*日").unwrap());
+// `30-07-20`, `07-30-20`, `30.07.2020`, `2020/07/30`
+static NUMERIC_DATE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d{1,4})[-./](\d{1,2})[-./](\d{1,4})").unwrap());
+// `30 de julio de 2020`, `30. Juli 2020`, `30 juillet 2020`, `30 July 2020`
+static DAY_MONTH_YEAR: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(\d{1,2})\.?\s+(?:de\s+)?(\p{L}+)\.?,?\s+(?:de\s+)?(\d{4})").unwrap()
+});
+// `July 30, 2020`, `Jul. 30 2020`
+static MONTH_DAY_YEAR: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\p{L}+)\.?\s+(\d{1,2}),?\s+(\d{4})").unwrap());
+
+// `12 horas`, `9 hrs`, `9 Std.`, `9 h`, `9 ore`, `9 時間`, `9時間12分`
+// The CJK units need no boundary, they are word characters like the digits that follow them
+static RUNTIME_HOURS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(\d+)\s*(?:時間|(?:hours?|hrs?|horas?|heures?|stunden?|std|ore|ora|h)(?:\b|\.|\s|$))",
+    )
+    .unwrap()
+});
+// `34 mins`, `34 minutos`, `34 Min.`, `34 minuti`, `34 分`
+static RUNTIME_MINUTES: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(\d+)\s*(?:分|(?:minutes?|minutos?|minuten?|minuti|minuto|mins?)(?:\b|\.|\s|$))",
+    )
+    .unwrap()
+});
+// `Less than 1 minute`, `Menos de 1 minuto`, `Weniger als 1 Minute`, `Moins d'une minute`,
+// `Meno di 1 minuto`, `1分未満`
+static RUNTIME_UNDER_A_MINUTE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)less than (?:1|a|one) minute|menos de (?:1|un) minuto|weniger als (?:1|eine) minute|moins d['’]une minute|moins de 1 minute|meno di (?:1|un) minuto|1\s*分未満",
+    )
+    .unwrap()
+});
+
+// `libro 3`, `Book 2.5`, `Band 1-3`
+static SERIES_POSITION: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(\d+(?:[.,]\d+)?(?:\s*-\s*\d+(?:[.,]\d+)?)?)").unwrap());
+
+/// Error returned when a release date cannot be extracted from a text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateError {
+    /// The text does not contain anything that looks like a date.
+    NotFound { text: String },
+    /// The text contains a date-like value that is not a valid calendar date.
+    Invalid { text: String, date: String },
+}
+
+impl DateError {
+    /// The text the date was extracted from.
+    pub fn text(&self) -> &str {
+        match self {
+            DateError::NotFound { text } | DateError::Invalid { text, .. } => text,
+        }
+    }
+}
+
+impl fmt::Display for DateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateError::NotFound { text } => write!(f, "No date found in {:?}", text.trim()),
+            DateError::Invalid { text, date } => {
+                write!(f, "Invalid date {:?} in {:?}", date, text.trim())
+            }
+        }
+    }
+}
+
+impl std::error::Error for DateError {}
+
+/// Extracts a date from a given text string, as rendered by the given marketplace.
+///
+/// The following formats are recognised:
+///
+/// * Numeric dates in the order used by the marketplace, with `-`, `.` or `/` as separators,
+///   e.g. `30-07-20` on audible.es, `07-30-20` on audible.com or `2020/07/30` on audible.co.jp.
+///   Numeric dates starting with a four digit year are always read as year, month and day.
+/// * Long-form dates with the month name in the language of the marketplace or in English,
+///   e.g. `30 de julio de 2020`, `30. Juli 2020`, `July 30, 2020` or `30 Jul 2020`.
+/// * Japanese dates, e.g. `2020年7月30日`.
+///
+/// Two-digit years are resolved to the century that places them no more than five years ahead
+/// of the current year, so `20` is read as 2020 and `95` as 1995.
 ///
 /// # Arguments
 ///
 /// * `text` - A string slice that holds the text containing the date.
+/// * `marketplace` - The storefront that rendered the text.
 ///
 /// # Returns
 ///
-/// * `Result<NaiveDate, Box<dyn std::error::Error>>` - A result containing the extracted date or an error.
+/// * `Result<NaiveDate, DateError>` - A result containing the extracted date or an error with the text that failed.
 ///
 /// # Examples
 ///
 /// ```
 /// use chrono::NaiveDate;
-/// use audible_scraper::extract_date;
+/// use audible_scraper::{extract_date, DateError, Marketplace};
 /// let text = "The event is scheduled for 12-05-21.";
-/// let date = extract_date(text).unwrap();
+/// let date = extract_date(text, Marketplace::Es).unwrap();
 /// assert_eq!(date, NaiveDate::from_ymd_opt(2021, 5, 12).unwrap());
+///
+/// let date = extract_date(text, Marketplace::Us).unwrap();
+/// assert_eq!(date, NaiveDate::from_ymd_opt(2021, 12, 5).unwrap());
+///
+/// let date = extract_date("30 de julio de 2020", Marketplace::Es).unwrap();
+/// assert_eq!(date, NaiveDate::from_ymd_opt(2020, 7, 30).unwrap());
+///
+/// let err = extract_date("Próximamente", Marketplace::Es).unwrap_err();
+/// assert_eq!(err, DateError::NotFound { text: "Próximamente".to_string() });
 /// ```
-pub fn extract_date(text: &str) -> Result<NaiveDate, Box<dyn std::error::Error>> {
-    // Define a regex pattern to capture any date in dd-mm-yy format
-    let re = Regex::new(r"(\d{2}-\d{2}-\d{2})")?;
+pub fn extract_date(text: &str, marketplace: Marketplace) -> Result<NaiveDate, DateError> {
+    extract_date_with_reference(text, marketplace, Utc::now().year())
+}
+
+fn extract_date_with_reference(
+    text: &str,
+    marketplace: Marketplace,
+    current_year: i32,
+) -> Result<NaiveDate, DateError> {
+    let invalid = |date: &str| DateError::Invalid {
+        text: text.to_string(),
+        date: date.to_string(),
+    };
+
+    if let Some(caps) = KANJI_DATE.captures(text) {
+        return ymd(&caps[1], &caps[2], &caps[3]).ok_or_else(|| invalid(&caps[0]));
+    }
+
+    if let Some(caps) = NUMERIC_DATE.captures(text) {
+        let (first, second, third) = (&caps[1], &caps[2], &caps[3]);
+        let order = match (marketplace.date_order(), first.len(), third.len()) {
+            (_, 4, _) => DateOrder::YearMonthDay,
+            (DateOrder::YearMonthDay, _, 4) => DateOrder::DayMonthYear,
+            (order, _, _) => order,
+        };
+        let (year, month, day) = match order {
+            DateOrder::YearMonthDay => (first, second, third),
+            DateOrder::DayMonthYear => (third, second, first),
+            DateOrder::MonthDayYear => (third, first, second),
+        };
+        let year = resolve_year(year, current_year).ok_or_else(|| invalid(&caps[0]))?;
+
+        return NaiveDate::from_ymd_opt(year, month.parse().unwrap_or(0), day.parse().unwrap_or(0))
+            .ok_or_else(|| invalid(&caps[0]));
+    }
+
+    for caps in DAY_MONTH_YEAR.captures_iter(text) {
+        if let Some(month) = month_number(&caps[2], marketplace) {
+            return ymd(&caps[3], &month.to_string(), &caps[1]).ok_or_else(|| invalid(&caps[0]));
+        }
+    }
+
+    for caps in MONTH_DAY_YEAR.captures_iter(text) {
+        if let Some(month) = month_number(&caps[1], marketplace) {
+            return ymd(&caps[3], &month.to_string(), &caps[2]).ok_or_else(|| invalid(&caps[0]));
+        }
+    }
+
+    Err(DateError::NotFound {
+        text: text.to_string(),
+    })
+}
+
+fn ymd(year: &str, month: &str, day: &str) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year.parse().ok()?, month.parse().ok()?, day.parse().ok()?)
+}
+
+/// Resolves a two or four digit year, placing two-digit years no more than five years ahead of
+/// `current_year`.
+fn resolve_year(year: &str, current_year: i32) -> Option<i32> {
+    let value: i32 = year.parse().ok()?;
+    match year.len() {
+        4 => Some(value),
+        1 | 2 => {
+            let century = current_year - current_year.rem_euclid(100);
+            let year = century + value;
+            Some(if year > current_year + 5 {
+                year - 100
+            } else {
+                year
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Finds the month (1-12) named by `word`, in the language of the marketplace or in English.
+///
+/// Abbreviations are accepted as long as they are at least three letters long and unambiguous,
+/// e.g. `jul`, `juil` or `févr`.
+fn month_number(word: &str, marketplace: Marketplace) -> Option<u32> {
+    let word = word.to_lowercase();
+    if word.chars().count() < 3 {
+        return None;
+    }
+
+    [marketplace.month_names(), Marketplace::Us.month_names()]
+        .into_iter()
+        .find_map(|names| {
+            let mut matches = names
+                .iter()
+                .enumerate()
+                .filter(|(_, name)| name.starts_with(word.as_str()));
+            match (matches.next(), matches.next()) {
+                (Some((i, _)), None) => Some(i as u32 + 1),
+                _ => None,
+            }
+        })
+}
+
+/// Extracts a runtime such as `Duración: 12 horas y 34 mins` from a given text string.
+///
+/// The hour and minute units of every marketplace are recognised, e.g. `9 hrs and 12 mins`,
+/// `9 Std. und 12 Min.`, `9 h et 12 min`, `9 ore e 12 min` or `9 時間 12 分`.
+/// Runtimes shorter than a minute, such as `Less than 1 minute`, are reported as one minute.
+///
+/// # Arguments
+///
+/// * `text` - A string slice that holds the text containing the runtime.
+///
+/// # Returns
+///
+/// * `Option<Duration>` - The runtime, or `None` if the text contains neither hours nor minutes.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use audible_scraper::extract_runtime;
+/// let runtime = extract_runtime("Duración: 12 horas y 34 mins").unwrap();
+/// assert_eq!(runtime, Duration::from_secs(12 * 3600 + 34 * 60));
+/// ```
+pub fn extract_runtime(text: &str) -> Option<Duration> {
+    if RUNTIME_UNDER_A_MINUTE.is_match(text) {
+        return Some(Duration::from_secs(60));
+    }
+
+    let capture = |re: &Regex| -> Option<u64> { re.captures(text)?[1].parse().ok() };
+
+    let hours = capture(&RUNTIME_HOURS);
+    let minutes = capture(&RUNTIME_MINUTES);
+    if hours.is_none() && minutes.is_none() {
+        return None;
+    }
+
+    Some(Duration::from_secs(
+        hours.unwrap_or(0) * 3600 + minutes.unwrap_or(0) * 60,
+    ))
+}
+
+/// Formats a runtime as a human readable string, e.g. `12 h 34 min`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use audible_scraper::format_runtime;
+/// assert_eq!(format_runtime(Duration::from_secs(45240)), "12 h 34 min");
+/// assert_eq!(format_runtime(Duration::from_secs(3600)), "1 h");
+/// assert_eq!(format_runtime(Duration::from_secs(660)), "11 min");
+/// ```
+pub fn format_runtime(runtime: Duration) -> String {
+    let minutes = runtime.as_secs() / 60;
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{} min", minutes),
+        (hours, 0) => format!("{} h", hours),
+        (hours, minutes) => format!("{} h {} min", hours, minutes),
+    }
+}
+
+/// Extracts the position of a book in a series, such as `libro 3` or `Book 2.5`.
+///
+/// Decimal commas are normalised to dots and ranges are kept, e.g. `Band 1,5` becomes `"1.5"`
+/// and `Books 1 - 3` becomes `"1-3"`.
+///
+/// # Examples
+///
+/// ```
+/// use audible_scraper::extract_series_position;
+/// assert_eq!(extract_series_position(", libro 3").as_deref(), Some("3"));
+/// assert_eq!(extract_series_position(", Book 2.5").as_deref(), Some("2.5"));
+/// assert_eq!(extract_series_position(" "), None);
+/// ```
+pub fn extract_series_position(text: &str) -> Option<String> {
+    let position = SERIES_POSITION.captures(text)?;
+    Some(
+        position[1]
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| if c == ',' { '.' } else { c })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_extract_date_numeric() {
+        let cases = [
+            (
+                Marketplace::Es,
+                "Fecha de lanzamiento: 30-07-20",
+                date(2020, 7, 30),
+            ),
+            (Marketplace::Us, "Release date: 07-30-20", date(2020, 7, 30)),
+            (
+                Marketplace::Ca,
+                "Release date: 07-30-2020",
+                date(2020, 7, 30),
+            ),
+            (Marketplace::Uk, "Release date: 30-07-20", date(2020, 7, 30)),
+            (
+                Marketplace::De,
+                "Erscheinungsdatum: 30.07.2020",
+                date(2020, 7, 30),
+            ),
+            (
+                Marketplace::Fr,
+                "Date de publication: 30/07/2020",
+                date(2020, 7, 30),
+            ),
+            (Marketplace::Jp, "配信日: 2020/07/30", date(2020, 7, 30)),
+            (Marketplace::Jp, "配信日: 20/07/30", date(2020, 7, 30)),
+            (
+                Marketplace::Us,
+                "Release date: 2020-07-30",
+                date(2020, 7, 30),
+            ),
+        ];
+
+        for (marketplace, text, expected) in cases {
+            assert_eq!(extract_date(text, marketplace), Ok(expected), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_extract_date_long_form() {
+        let cases = [
+            (Marketplace::Es, "30 de julio de 2020", date(2020, 7, 30)),
+            (Marketplace::Es, "1 de sept. de 2021", date(2021, 9, 1)),
+            (Marketplace::De, "30. Juli 2020", date(2020, 7, 30)),
+            (Marketplace::De, "3. März 2021", date(2021, 3, 3)),
+            (Marketplace::Fr, "30 juillet 2020", date(2020, 7, 30)),
+            (Marketplace::Fr, "2 févr. 2021", date(2021, 2, 2)),
+            (Marketplace::It, "30 luglio 2020", date(2020, 7, 30)),
+            (Marketplace::Uk, "30 July 2020", date(2020, 7, 30)),
+            (
+                Marketplace::Us,
+                "Release date: July 30, 2020",
+                date(2020, 7, 30),
+            ),
+            (Marketplace::Us, "Jul. 30 2020", date(2020, 7, 30)),
+            (Marketplace::Jp, "2020年7月30日", date(2020, 7, 30)),
+            // English month names are understood on every marketplace
+            (Marketplace::Es, "30 July 2020", date(2020, 7, 30)),
+        ];
+
+        for (marketplace, text, expected) in cases {
+            assert_eq!(extract_date(text, marketplace), Ok(expected), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_extract_date_two_digit_years() {
+        let cases = [
+            ("01-01-00", date(2000, 1, 1)),
+            ("30-07-20", date(2020, 7, 30)),
+            ("30-07-29", date(2029, 7, 30)),
+            ("30-07-30", date(1930, 7, 30)),
+            ("30-07-95", date(1995, 7, 30)),
+        ];
+
+        for (text, expected) in cases {
+            assert_eq!(
+                extract_date_with_reference(text, Marketplace::Es, 2024),
+                Ok(expected),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_extract_runtime() {
+        let minutes = |minutes: u64| Some(Duration::from_secs(minutes * 60));
+        let cases = [
+            ("Duración: 12 horas y 34 mins", minutes(12 * 60 + 34)),
+            ("Duración: 1 hora y 1 min", minutes(61)),
+            ("Duración: 11 mins", minutes(11)),
+            ("Duración: 3 horas", minutes(180)),
+            ("Length: 9 hrs and 12 mins", minutes(9 * 60 + 12)),
+            ("Length: 1 hr", minutes(60)),
+            ("Spieldauer: 9 Std. und 12 Min.", minutes(9 * 60 + 12)),
+            ("Durée : 9 h et 12 min", minutes(9 * 60 + 12)),
+            ("Durata: 9 ore e 12 min", minutes(9 * 60 + 12)),
+            ("再生時間: 9 時間 12 分", minutes(9 * 60 + 12)),
+            ("再生時間: 9時間12分", minutes(9 * 60 + 12)),
+            ("再生時間: 9時間", minutes(9 * 60)),
+            ("再生時間: 45分", minutes(45)),
+            ("Duración: pronto", None),
+            // Units must be whole words
+            ("Length: 9 months", None),
+        ];
+
+        for (text, expected) in cases {
+            assert_eq!(extract_runtime(text), expected, "{text}");
+        }
+    }
+
+    #[test]
+    fn test_extract_runtime_under_a_minute() {
+        let cases = [
+            "Length: Less than 1 minute",
+            "Duración: Menos de 1 minuto",
+            "Spieldauer: Weniger als 1 Minute",
+            "Durée : Moins d'une minute",
+            "Durata: Meno di 1 minuto",
+            "再生時間: 1分未満",
+        ];
 
-    // Find the date in the text
-    let date_str = re
-        .captures(text)
-        .and_then(|caps| caps.get(1).map(|m| m.as_str()))
-        .ok_or("Date not found")?;
+        for text in cases {
+            assert_eq!(
+                extract_runtime(text),
+                Some(Duration::from_secs(60)),
+                "{text}"
+            );
+        }
+    }
 
-    // Parse the date string to a NaiveDate object using the format dd-mm-yy
-    let date = NaiveDate::parse_from_str(date_str, "%d-%m-%y")?;
+    #[test]
+    fn test_extract_date_errors() {
+        assert_eq!(
+            extract_date("Fecha de lanzamiento: pronto", Marketplace::Es),
+            Err(DateError::NotFound {
+                text: "Fecha de lanzamiento: pronto".to_string()
+            })
+        );
+        assert_eq!(
+            extract_date("Release date: 30-07-20", Marketplace::Us),
+            Err(DateError::Invalid {
+                text: "Release date: 30-07-20".to_string(),
+                date: "30-07-20".to_string()
+            })
+        );
+        assert_eq!(
+            extract_date("31 de febrero de 2020", Marketplace::Es),
+            Err(DateError::Invalid {
+                text: "31 de febrero de 2020".to_string(),
+                date: "31 de febrero de 2020".to_string()
+            })
+        );
+        // Ambiguous abbreviations are not guessed
+        assert!(extract_date("3 ju 2020", Marketplace::Fr).is_err());
 
-    Ok(date)
+        let err = extract_date("Release date: 30-07-20", Marketplace::Us).unwrap_err();
+        assert_eq!(err.text(), "Release date: 30-07-20");
+        assert_eq!(
+            err.to_string(),
+            r#"Invalid date "30-07-20" in "Release date: 30-07-20""#
+        );
+    }
 }